@@ -1,4 +1,6 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
+// The tests only exercise the decision logic, leaving everything driving the runtime unused
+#![cfg_attr(test, allow(dead_code))]
 #![warn(
     clippy::complexity,
     clippy::correctness,
@@ -8,24 +10,57 @@
     rust_2018_idioms
 )]
 
+use core::{
+    cell::Cell,
+    fmt::{self, Write},
+};
+
+use arrayvec::{ArrayString, ArrayVec};
 use asr::{
     file_format::pe,
     future::{next_tick, retry},
-    settings::{gui::Title, Gui},
+    settings::{self, gui::Title, Gui},
     signature::Signature,
     time::Duration,
     timer::{self, TimerState},
-    watcher::Watcher,
-    Address, Process,
+    watcher::{Pair, Watcher},
+    Address, Error, Process,
 };
 
+#[cfg(not(test))]
 asr::async_main!(stable);
+#[cfg(not(test))]
 asr::panic_handler!();
 
 const PROCESS_NAMES: &[&str] = &["Croc64.exe"];
+/// Highest raw level ID used by the game (5-B1)
+const MAX_LEVEL_ID: u32 = 54;
+/// Number of consecutive valid memory reads required after attaching before the splitter acts
+const WARMUP_READS: u8 = 2;
+/// How long the game status has to stay unrecognized before the game is considered stuck
+const STALL_THRESHOLD: Duration = Duration::seconds(10);
+const TICK_RATE: f64 = 120.0;
+/// Number of failed attach self-tests, a second apart, before the addresses are scanned for again
+const SELF_TEST_RETRIES: u32 = 10;
+/// Version of the settings layout, stored alongside the settings themselves
+const SETTINGS_VERSION: i64 = 1;
+/// Settings that got renamed, as (version of the rename, old key, new key)
+const RENAMED_SETTINGS: &[(i64, &str, &str)] = &[];
+/// Minimum time a level must have been running before its completion flag is trusted
+const MIN_LEVEL_TIME: Duration = Duration::seconds(2);
+/// Settings key of the custom split sequence, see [`SplitSequence::parse`] for its format. The
+/// settings GUI has no text fields, so it's set by editing the stored settings directly
+const SPLIT_SEQUENCE_KEY: &str = "custom_split_sequence";
 
 async fn main() {
+    migrate_settings();
     let mut settings = Settings::register();
+    asr::set_tick_rate(TICK_RATE);
+
+    // Run accumulators are kept outside the attach loop, so they can survive the game briefly closing mid-run
+    let mut run_state = RunState::default();
+    let mut control = TimerControl::new();
+    let mut split_sequence = SplitSequence::default();
 
     loop {
         // Hook to the target process
@@ -36,56 +71,107 @@ async fn main() {
         })
         .await;
 
+        // A new attach only continues the previous accumulators if the same run is still going on
+        if !settings.keep_run_state_on_reattach || control.state().eq(&TimerState::NotRunning) {
+            run_state = RunState::default();
+        }
+
         process
             .until_closes(async {
                 // Once the target has been found and attached to, set up some default watchers
                 let mut watchers = Watchers::default();
+                let mut last_tick = TimerSnapshot::take(&control);
 
-                // Perform memory scanning to look for the addresses we need
-                let addresses = Memory::init(&process, process_name).await;
+                // Perform memory scanning to look for the addresses we need. A signature resolving to
+                // unrelated data is better caught now than halfway through a run, so the addresses are
+                // checked right away and scanned for again if they don't hold plausible values
+                let mut failed_self_tests: u32 = 0;
+                let mut addresses = Memory::init(&process, process_name).await;
+                let addresses = loop {
+                    match addresses.self_test(&process) {
+                        Some((game_status, level_id)) => {
+                            asr::print_message("Attach self-test passed");
+                            if settings.verbose_logging {
+                                asr::print_limited::<128>(&format_args!(
+                                    "Game status {}, level ID {}",
+                                    game_status, level_id as i32
+                                ));
+                            }
+                            break addresses;
+                        }
+                        None => {
+                            // Keep the log readable while the game is still starting up
+                            if failed_self_tests.is_multiple_of(SELF_TEST_RETRIES) {
+                                asr::print_message(
+                                    "Attach self-test failed, retrying every second",
+                                );
+                            }
+                            failed_self_tests += 1;
 
-                loop {
-                    // Splitting logic. Adapted from OG LiveSplit:
-                    // Order of execution
-                    // 1. update() will always be run first. There are no conditions on the execution of this action.
-                    // 2. If the timer is currently either running or paused, then the isLoading, gameTime, and reset actions will be run.
-                    // 3. If reset does not return true, then the split action will be run.
-                    // 4. If the timer is currently not running (and not paused), then the start action will be run.
-                    settings.update();
-                    update_loop(&process, &addresses, &mut watchers);
+                            if settings.verbose_logging {
+                                asr::print_limited::<128>(&format_args!(
+                                    "Game status {:?} at {}, level ID {:?} at {}",
+                                    addresses.read_u32(&process, addresses.game_status),
+                                    addresses.game_status,
+                                    addresses.read_u32(&process, addresses.level_id),
+                                    addresses.level_id,
+                                ));
+                            }
 
-                    if [TimerState::Running, TimerState::Paused].contains(&timer::state()) {
-                        match is_loading(&watchers, &settings) {
-                            Some(true) => timer::pause_game_time(),
-                            Some(false) => timer::resume_game_time(),
-                            _ => (),
-                        }
+                            for _ in 0..TICK_RATE as u32 {
+                                next_tick().await;
+                            }
 
-                        match game_time(&watchers, &settings, &addresses) {
-                            Some(x) => timer::set_game_time(x),
-                            _ => (),
+                            // While booting, the game goes through states that aren't decoded, so
+                            // the addresses are given some time before they're scanned for again.
+                            // A full signature scan every second would weigh on the game for nothing
+                            if failed_self_tests.is_multiple_of(SELF_TEST_RETRIES) {
+                                addresses = Memory::init(&process, process_name).await;
+                            }
                         }
+                    }
+                };
 
-                        match reset(&watchers, &settings) {
-                            true => timer::reset(),
-                            _ => match split(&watchers, &settings) {
-                                true => timer::split(),
-                                _ => (),
-                            },
-                        }
+                // Report the size of the main module once. No table of released builds exists to
+                // name the version from, but the size alone tells builds apart in bug reports
+                let mut build = ArrayString::<16>::new();
+                let _ = write!(build, "0x{:X}", addresses.image_size);
+                timer::set_variable("Build", &build);
+                timer::set_variable("SigSet", addresses.signature_set.name());
+
+                // Game time might have been left paused by a load when the game closed. Evaluate it
+                // right away instead of inheriting that state, which could leave it frozen for good
+                if [TimerState::Running, TimerState::Paused].contains(&control.state()) {
+                    update_loop(&process, &addresses, &mut watchers);
+                    match starts_paused(&watchers, &settings) {
+                        true => control.pause_game_time(),
+                        false => control.resume_game_time(),
                     }
+                }
 
-                    if timer::state().eq(&TimerState::NotRunning) && start(&watchers, &settings) {
-                        timer::start();
-                        timer::pause_game_time();
+                loop {
+                    let settings_map = settings::Map::load();
+                    settings.update_from(&settings_map);
+                    split_sequence.update(&settings_map);
+                    update_loop(&process, &addresses, &mut watchers);
+                    control.update(
+                        settings.dry_run,
+                        run_state.game_time,
+                        watchers.level.pair.and_then(|val| val.current),
+                    );
 
-                        match is_loading(&watchers, &settings) {
-                            Some(true) => timer::pause_game_time(),
-                            Some(false) => timer::resume_game_time(),
-                            _ => (),
-                        }
+                    if settings.clear_level_bests {
+                        clear_level_bests();
                     }
 
+                    drive_timer(
+                        &mut control,
+                        &mut watchers,
+                        &settings,
+                        &split_sequence,
+                        &mut run_state,
+                        &mut last_tick,
+                    );
                     next_tick().await;
                 }
             })
@@ -93,6 +179,474 @@ async fn main() {
     }
 }
 
+/// Timer state and split index, as the splitter left them at the end of a tick
+#[derive(Copy, Clone)]
+struct TimerSnapshot {
+    state: TimerState,
+    split_index: Option<u64>,
+}
+
+impl TimerSnapshot {
+    fn take(timer: &impl Timer) -> Self {
+        Self {
+            state: timer.state(),
+            split_index: timer.split_index(),
+        }
+    }
+}
+
+/// Decides and issues the timer operations of a tick, once the watchers are up to date. It
+/// doesn't touch the runtime directly, so the tests replay recorded reads through it as well.
+///
+/// Splitting logic. Adapted from OG LiveSplit:
+/// Order of execution
+/// 1. update() will always be run first. There are no conditions on the execution of this action.
+/// 2. If the timer is currently either running or paused, then the isLoading, gameTime, and reset actions will be run.
+/// 3. If reset does not return true, then the split action will be run.
+/// 4. If the timer is currently not running (and not paused), then the start action will be run.
+fn drive_timer(
+    timer: &mut impl Timer,
+    watchers: &mut Watchers,
+    settings: &Settings,
+    split_sequence: &SplitSequence,
+    run_state: &mut RunState,
+    last_tick: &mut TimerSnapshot,
+) {
+    // Cheap enough to compute every tick, unlike comparing the settings it comes from
+    let mut split_levels = SplitLevels::new(settings, split_sequence, run_state.sequence_position);
+
+    // The timer state and split index are recorded at the end of every tick, so
+    // any change since then comes from the user operating the timer manually.
+    // Manual splits, undos and skips only show up in the split index
+    let timer_state = timer.state();
+    let user_acted =
+        timer_state.ne(&last_tick.state) || timer.split_index().ne(&last_tick.split_index);
+
+    if user_acted {
+        match timer_state {
+            // However the run ended (reset by the user or aborted right after
+            // starting), nothing from it should linger into the next attempt
+            TimerState::NotRunning => run_state.reset(settings.full_reset),
+            // A run started by hand (eg. with auto start disabled) gets its game
+            // time set up the same way as one started by the splitter
+            TimerState::Running if last_tick.state.eq(&TimerState::NotRunning) => {
+                match starts_paused(watchers, settings) {
+                    true => timer.pause_game_time(),
+                    false => timer.resume_game_time(),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    // The attract demo plays actual levels, which would otherwise drive the timer
+    // the same way a run does
+    let in_demo = settings.ignore_demo_mode
+        && watchers
+            .game_status
+            .pair
+            .is_some_and(|val| val.current.eq(&GameStatus::DemoMode));
+
+    if watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.changed_from_to(&GameStatus::WorldMap, &GameStatus::InGame))
+    {
+        run_state.level_game_time = Duration::ZERO;
+    }
+
+    // Only completions the splitter would split on are counted, as a glitched
+    // rise of the flag would otherwise inflate the sum or get stored as a best for good
+    if !in_demo && timer_state.eq(&TimerState::Running) && level_completed(watchers) {
+        if let Some(level) = watchers.last_completed_level {
+            let time = run_state.level_game_time;
+
+            // Completing a level again replaces its time rather than adding to the sum
+            run_state.level_times[level.ordinal()] = time;
+            timer.set_duration_variable("Sum IGT", run_state.level_times.iter().sum());
+
+            if run_state
+                .furthest_level
+                .is_none_or(|furthest| level.ordinal() > furthest.ordinal())
+            {
+                run_state.furthest_level = Some(level);
+            }
+
+            // Completing a level further down the sequence means the route
+            // deviated from it. Rather than stalling every split that follows,
+            // the sequence picks up from there
+            if let Some(position) = split_sequence
+                .find(level, run_state.sequence_position)
+                .filter(|&position| position > run_state.sequence_position)
+            {
+                timer.print(format_args!(
+                    "Custom split sequence resynced at {} (entry {})",
+                    level.code(),
+                    position + 1
+                ));
+                run_state.sequence_position = position;
+                split_levels =
+                    SplitLevels::new(settings, split_sequence, run_state.sequence_position);
+            }
+
+            if settings.track_level_bests {
+                let beaten = timer.record_level_best(level, time);
+                timer.set_variable(
+                    "Level Best",
+                    match beaten {
+                        true => "Beaten",
+                        false => "Missed",
+                    },
+                );
+            }
+        }
+    }
+
+    if watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.changed_from_to(&GameStatus::InGame, &GameStatus::WorldMap))
+    {
+        if let Some(exit) = watchers.level_exit {
+            timer.set_variable("Level Exit", exit.name());
+        }
+    }
+
+    // Right after attaching, the watcher pairs are only half initialized. Wait
+    // for a couple of consecutive valid reads before acting on any transition.
+    // Also back off for a tick after a manual timer operation, so it doesn't get
+    // immediately undone by a transition that happened at the same time
+    let warmed_up = watchers.valid_reads >= WARMUP_READS && !user_acted;
+
+    if settings.verbose_logging {
+        let mut debug = ArrayString::<32>::new();
+        let _ = write!(
+            debug,
+            "S:{} Sp:{} R:{} L:{}",
+            start(watchers, settings) as u8,
+            split(watchers, settings, &split_levels, run_state) as u8,
+            reset(watchers, settings, run_state) as u8,
+            match is_loading(watchers, settings) {
+                Some(true) => "1",
+                Some(false) => "0",
+                None => "-",
+            }
+        );
+        timer.set_variable("Debug", &debug);
+
+        let mut level_id = ArrayString::<16>::new();
+        match watchers.raw_level_id {
+            Some(id) => {
+                let _ = write!(level_id, "{}", id as i32);
+            }
+            None => level_id.push('-'),
+        }
+        timer.set_variable("Level ID", &level_id);
+    }
+
+    if !in_demo && [TimerState::Running, TimerState::Paused].contains(&timer.state()) {
+        let loading = is_loading(watchers, settings);
+        match loading {
+            Some(true) => {
+                timer.pause_game_time();
+                run_state.load_time += tick_duration();
+            }
+            Some(false) => timer.resume_game_time(),
+            _ => (),
+        }
+
+        if timer.state().eq(&TimerState::Running) && loading.ne(&Some(true)) {
+            run_state.game_time += tick_duration();
+            run_state.segment_time += tick_duration();
+            run_state.level_game_time += tick_duration();
+        }
+
+        timer.set_duration_variable("Load Time", run_state.load_time);
+        timer.set_duration_variable("Segment", run_state.segment_time);
+
+        match game_time(watchers, settings) {
+            Some(x) => timer.set_game_time(x),
+            _ => (),
+        }
+
+        if warmed_up
+            && settings.level_regression.eq(&LevelRegression::Warn)
+            && level_regressed(watchers, run_state)
+        {
+            timer.print(format_args!(
+                "Warning: entered a level behind the furthest one completed in the run"
+            ));
+        }
+
+        match warmed_up && reset(watchers, settings, run_state) {
+            true => {
+                timer.reset();
+                run_state.reset(settings.full_reset);
+            }
+            _ if warmed_up && start_split(watchers, run_state) => {
+                timer.split();
+                run_state.segment_time = Duration::ZERO;
+                run_state.start_split_pending = false;
+            }
+            _ => match warmed_up && split(watchers, settings, &split_levels, run_state) {
+                true => {
+                    run_state.grouped_completions += 1;
+                    if split_sequence.is_active() {
+                        run_state.sequence_position += 1;
+                    }
+
+                    // A delayed split can go off after the next level has been entered, so
+                    // the level split on is taken from the last completion
+                    let split_level = watchers.last_completion.map(|(level, _)| level);
+
+                    // The final boss ends the run, as does the single level of a
+                    // practice run, so they always split even if the current
+                    // group is still incomplete
+                    let run_ended = settings.race_practice
+                        || split_level.is_some_and(|level| !level.returns_to_world_map());
+
+                    // Nothing is left to split on after the last level of the
+                    // splits, so its group is closed there as well
+                    let last_split = match split_sequence.is_active() {
+                        true => split_sequence.next(run_state.sequence_position).is_none(),
+                        false => {
+                            split_level.is_some_and(|level| split_levels.last() == Some(level))
+                        }
+                    };
+
+                    // Map transitions split twice per level, so they can't be grouped by
+                    // level completions
+                    let group_size = match settings.split_map_transitions {
+                        true => 1,
+                        false => settings.split_grouping.size(),
+                    };
+
+                    if run_ended || last_split || run_state.grouped_completions >= group_size {
+                        timer.split();
+                        run_state.segment_time = Duration::ZERO;
+                        run_state.grouped_completions = 0;
+                        run_state.run_finished = run_ended || last_split;
+
+                        // Splitting on a level entry (with map transitions), the
+                        // level load a post-split pause would last up to is already
+                        // happening. Starting one would pause the whole level instead
+                        let entered_level = watchers.game_status.pair.is_some_and(|val| {
+                            val.changed_from_to(&GameStatus::WorldMap, &GameStatus::InGame)
+                        });
+                        if !(entered_level && settings.post_split_pause_until_level_load) {
+                            watchers.time_since_split = Some(Duration::ZERO);
+                        }
+                    }
+                }
+                _ => (),
+            },
+        }
+
+        // Taken from the timer, so manual splits and undos are accounted for
+        if let Some(index) = timer.split_index() {
+            let mut splits = ArrayString::<16>::new();
+            let _ = write!(splits, "{}", index);
+            timer.set_variable("Splits", &splits);
+        }
+    }
+
+    // When the logos are left to play out, the intro ends into the attract demo.
+    // That's the moment a run timed from boot starts, so it goes through regardless
+    let intro_ended_into_demo = watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.changed_from_to(&GameStatus::Intro, &GameStatus::DemoMode));
+
+    if (!in_demo || intro_ended_into_demo)
+        && timer.state().eq(&TimerState::NotRunning)
+        && warmed_up
+        && start(watchers, settings)
+    {
+        timer.start();
+        run_state.reset(settings.full_reset);
+        // In race practice the run starts in the level itself, there's no startup segment
+        run_state.start_split_pending = settings.split_on_run_start && !settings.race_practice;
+
+        if settings.race_practice {
+            if let Some(level) = watchers.level.pair.and_then(|val| val.current) {
+                timer.set_variable("Practice Level", level.code());
+            }
+        }
+
+        match starts_paused(watchers, settings) {
+            true => timer.pause_game_time(),
+            false => timer.resume_game_time(),
+        }
+    }
+
+    *last_tick = TimerSnapshot::take(timer);
+}
+
+/// Everything the splitter does with the timer, so the logic deciding on it can run against
+/// [`TimerControl`] as well as against a timer simulated by the tests
+trait Timer {
+    fn state(&self) -> TimerState;
+    /// Index of the current segment, `None` without a run going on
+    fn split_index(&self) -> Option<u64>;
+    fn start(&mut self);
+    fn split(&mut self);
+    fn reset(&mut self);
+    fn pause_game_time(&mut self);
+    fn resume_game_time(&mut self);
+    fn set_game_time(&mut self, game_time: Duration);
+    fn set_variable(&mut self, key: &str, value: &str);
+    /// See [`record_level_best`]
+    fn record_level_best(&mut self, level: Level, time: Duration) -> bool;
+    fn print(&mut self, message: fmt::Arguments<'_>);
+
+    fn set_duration_variable(&mut self, key: &str, value: Duration) {
+        let mut buf = ArrayString::<32>::new();
+        let _ = write!(
+            buf,
+            "{:02}:{:02}:{:02}.{:03}",
+            value.whole_hours(),
+            value.whole_minutes() % 60,
+            value.whole_seconds() % 60,
+            value.subsec_milliseconds()
+        );
+        self.set_variable(key, &buf);
+    }
+}
+
+/// Issues the timer operations. In dry run mode they're logged against a simulated timer
+/// instead, so the timeline of a whole run can be captured without the timer being touched
+struct TimerControl {
+    dry_run: bool,
+    simulated_state: TimerState,
+    simulated_split_index: u64,
+    game_time_paused: bool,
+    game_time: Duration,
+    level: Option<Level>,
+}
+
+impl TimerControl {
+    const fn new() -> Self {
+        Self {
+            dry_run: false,
+            simulated_state: TimerState::NotRunning,
+            simulated_split_index: 0,
+            game_time_paused: false,
+            game_time: Duration::ZERO,
+            level: None,
+        }
+    }
+
+    /// Refreshes the mode and the context events get logged with
+    fn update(&mut self, dry_run: bool, game_time: Duration, level: Option<Level>) {
+        // Switching in the middle of a run would leave it split between the two timers, so the
+        // mode only changes in between runs
+        if dry_run != self.dry_run && self.state().eq(&TimerState::NotRunning) {
+            self.dry_run = dry_run;
+            self.simulated_state = TimerState::NotRunning;
+            self.game_time_paused = false;
+        }
+        self.game_time = game_time;
+        self.level = level;
+    }
+
+    fn log(&self, event: &str) {
+        asr::print_limited::<128>(&format_args!(
+            "[Dry run] {} at {}.{:03}s ({})",
+            event,
+            self.game_time.whole_seconds(),
+            self.game_time.subsec_milliseconds(),
+            self.level.map_or("no level", Level::code),
+        ));
+    }
+}
+
+impl Timer for TimerControl {
+    fn state(&self) -> TimerState {
+        match self.dry_run {
+            true => self.simulated_state,
+            false => timer::state(),
+        }
+    }
+
+    fn split_index(&self) -> Option<u64> {
+        match self.dry_run {
+            true => self
+                .simulated_state
+                .ne(&TimerState::NotRunning)
+                .then_some(self.simulated_split_index),
+            false => timer::current_split_index(),
+        }
+    }
+
+    fn start(&mut self) {
+        match self.dry_run {
+            true => {
+                self.log("Start");
+                self.simulated_state = TimerState::Running;
+                self.simulated_split_index = 0;
+            }
+            false => timer::start(),
+        }
+    }
+
+    fn split(&mut self) {
+        match self.dry_run {
+            true => {
+                self.log("Split");
+                self.simulated_split_index += 1;
+            }
+            false => timer::split(),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self.dry_run {
+            true => {
+                self.log("Reset");
+                self.simulated_state = TimerState::NotRunning;
+            }
+            false => timer::reset(),
+        }
+    }
+
+    fn pause_game_time(&mut self) {
+        match self.dry_run {
+            true if !self.game_time_paused => self.log("Load started"),
+            true => (),
+            false => timer::pause_game_time(),
+        }
+        self.game_time_paused = true;
+    }
+
+    fn resume_game_time(&mut self) {
+        match self.dry_run {
+            true if self.game_time_paused => self.log("Load ended"),
+            true => (),
+            false => timer::resume_game_time(),
+        }
+        self.game_time_paused = false;
+    }
+
+    fn set_game_time(&mut self, game_time: Duration) {
+        if !self.dry_run {
+            timer::set_game_time(game_time);
+        }
+    }
+
+    fn set_variable(&mut self, key: &str, value: &str) {
+        timer::set_variable(key, value);
+    }
+
+    /// A dry run only compares against the stored best, it doesn't replace it
+    fn record_level_best(&mut self, level: Level, time: Duration) -> bool {
+        record_level_best(level, time, !self.dry_run)
+    }
+
+    fn print(&mut self, message: fmt::Arguments<'_>) {
+        asr::print_limited::<128>(&message);
+    }
+}
+
 #[derive(Gui)]
 struct Settings {
     /// General settings
@@ -100,8 +654,224 @@ struct Settings {
     /// Enable auto start
     #[default = true]
     start: bool,
+    /// Start the timer when the intro logos end instead of on a new game (for runs timed from boot)
+    #[default = false]
+    start_on_intro_end: bool,
+    /// Race practice: start on entering any level and stop on its completion
+    #[default = false]
+    race_practice: bool,
+    /// Keep run statistics if the game is restarted mid-run
+    #[default = true]
+    keep_run_state_on_reattach: bool,
+    /// Ignore everything happening in the attract demo (recommended)
+    #[default = true]
+    ignore_demo_mode: bool,
+    /// Load removal
+    _load_removal: Title,
+    /// Pause game time while entering a level from the world map
+    #[default = false]
+    pause_on_level_load: bool,
+    /// Level load pause duration
+    level_load_pause: ShortDuration,
+    /// Pause game time while leaving a level for the world map
+    #[default = false]
+    pause_on_level_exit: bool,
+    /// Level exit pause duration
+    level_exit_pause: ShortDuration,
+    /// Pause game time while going to or coming back from the main menu
+    #[default = false]
+    pause_on_menu_transition: bool,
+    /// Menu transition pause duration
+    menu_transition_pause: ShortDuration,
+    /// Pause game time during the intro logos and the attract demo
+    #[default = false]
+    pause_during_intro: bool,
+    /// Pause game time whenever not in gameplay (approximation, not true load removal)
+    #[default = false]
+    pause_outside_gameplay: bool,
+    /// Pause game time after each split (eg. to exclude the results screen)
+    #[default = false]
+    pause_after_split: bool,
+    /// Post-split pause duration
+    post_split_pause: ShortDuration,
+    /// Keep the post-split pause going until the next level loads
+    #[default = false]
+    post_split_pause_until_level_load: bool,
+    /// Stop game time on level completion until the world map is reached
+    #[default = false]
+    pause_on_results_screen: bool,
+    /// Pause game time if the game appears to be stuck
+    #[default = false]
+    pause_on_stall: bool,
+    /// Pause game time on level entry
+    _level_entry_pause: Title,
+    /// Pause game time when entering the levels selected below (eg. to skip unskippable intros)
+    #[default = false]
+    pause_on_level_entry: bool,
+    /// Level entry pause duration
+    level_entry_pause: ShortDuration,
+    /// 1-1 - And So The Adventure Begins
+    #[default = false]
+    pause_1_1: bool,
+    /// 1-2 - Underground Overground
+    #[default = false]
+    pause_1_2: bool,
+    /// 1-3 - Shoutin Lava Lava Lava
+    #[default = false]
+    pause_1_3: bool,
+    /// 1-B1 - Lair of the Feeble
+    #[default = false]
+    pause_1_b1: bool,
+    /// 1-S1 - The Curvy Caverns
+    #[default = false]
+    pause_1_s1: bool,
+    /// 1-4 - The Tumbling Dantini
+    #[default = false]
+    pause_1_4: bool,
+    /// 1-5 - Cave Fear
+    #[default = false]
+    pause_1_5: bool,
+    /// 1-6 - Darkness Descends
+    #[default = false]
+    pause_1_6: bool,
+    /// 1-B2 - Fight Night with Flibby
+    #[default = false]
+    pause_1_b2: bool,
+    /// 1-S2 - The Twisty Tunnels
+    #[default = false]
+    pause_1_s2: bool,
+    /// 2-1 - The Ice of Life
+    #[default = false]
+    pause_2_1: bool,
+    /// 2-2 - Be Wheely Careful
+    #[default = false]
+    pause_2_2: bool,
+    /// 2-3 - Riot Brrrrr
+    #[default = false]
+    pause_2_3: bool,
+    /// 2-B1 - Chumly's Snow Den
+    #[default = false]
+    pause_2_b1: bool,
+    /// 2-S1 - Clouds of Ice
+    #[default = false]
+    pause_2_s1: bool,
+    /// 2-4 - I Snow Him So Well
+    #[default = false]
+    pause_2_4: bool,
+    /// 2-5 - Say No Snow
+    #[default = false]
+    pause_2_5: bool,
+    /// 2-6 - Licence to Chill
+    #[default = false]
+    pause_2_6: bool,
+    /// 2-B2 - Demon Itsy's Ice Palace
+    #[default = false]
+    pause_2_b2: bool,
+    /// 2-S2 - Ice Bridge to Eternity
+    #[default = false]
+    pause_2_s2: bool,
+    /// 3-1 - Lights, Camel, Action!
+    #[default = false]
+    pause_3_1: bool,
+    /// 3-2 - Mud Pit Mania
+    #[default = false]
+    pause_3_2: bool,
+    /// 3-3 - Goin' Underground
+    #[default = false]
+    pause_3_3: bool,
+    /// 3-B1 - The Deadly Tank of Neptuna
+    #[default = false]
+    pause_3_b1: bool,
+    /// 3-S1 - Arabian Heights
+    #[default = false]
+    pause_3_s1: bool,
+    /// 3-4 - Sand and Freedom
+    #[default = false]
+    pause_3_4: bool,
+    /// 3-5 - Leap of Faith
+    #[default = false]
+    pause_3_5: bool,
+    /// 3-6 - Life's a Beach
+    #[default = false]
+    pause_3_6: bool,
+    /// 3-B2 - Cactus Jack's Ranch
+    #[default = false]
+    pause_3_b2: bool,
+    /// 3-S2 - Defeato Burrito
+    #[default = false]
+    pause_3_s2: bool,
+    /// 4-1 - The Tower of Power
+    #[default = false]
+    pause_4_1: bool,
+    /// 4-2 - Hassle in the Castle
+    #[default = false]
+    pause_4_2: bool,
+    /// 4-3 - Dungeon of Defright
+    #[default = false]
+    pause_4_3: bool,
+    /// 4-B1 - Fosley's Freaky Donut
+    #[default = false]
+    pause_4_b1: bool,
+    /// 4-S1 - Smash and See
+    #[default = false]
+    pause_4_s1: bool,
+    /// 4-4 - Ballistic Meg's Fairway
+    #[default = false]
+    pause_4_4: bool,
+    /// 4-5 - Swipe Swiftly's Wicked Ride
+    #[default = false]
+    pause_4_5: bool,
+    /// 4-6 - Panic at Platform Pete's Lair
+    #[default = false]
+    pause_4_6: bool,
+    /// 4-B2 - Baron Dante's Funky Inferno
+    #[default = false]
+    pause_4_b2: bool,
+    /// 4-S2 - Jailhouse Croc
+    #[default = false]
+    pause_4_s2: bool,
+    /// 5-1 - And So The Adventure Returns
+    #[default = false]
+    pause_5_1: bool,
+    /// 5-2 - Diet Brrrrrrr
+    #[default = false]
+    pause_5_2: bool,
+    /// 5-3 - Trial on the Nile
+    #[default = false]
+    pause_5_3: bool,
+    /// 5-4 - Crox Interactive
+    #[default = false]
+    pause_5_4: bool,
+    /// 5-B1 - Secret Sentinel
+    #[default = false]
+    pause_5_b1: bool,
+    /// Reset
+    _reset: Title,
+    /// Action on entering a level before the furthest one completed (secret levels excepted)
+    level_regression: LevelRegression,
+    /// Clear run statistics (eg. load time) on reset
+    #[default = true]
+    full_reset: bool,
     /// Level splitting
     _level: Title,
+    /// Levels to split on
+    category: Category,
+    /// Split boss levels when getting back to the hub instead of on completion
+    #[default = false]
+    split_boss_on_hub: bool,
+    /// Only split once the world map is reached after a level completion
+    #[default = false]
+    confirm_split_on_world_map: bool,
+    /// Minimum game time before the first split is allowed
+    split_arm_delay: ShortDuration,
+    /// Split both when entering and when leaving a level (ignores the individual level settings, backing out of a level splits neither way)
+    #[default = false]
+    split_map_transitions: bool,
+    /// Split on first entering a level after the start (for layouts with a startup segment)
+    #[default = false]
+    split_on_run_start: bool,
+    /// Split grouping (intermediate level completions don't split, ignored with map transitions)
+    split_grouping: SplitGrouping,
     /// 1-1 - And So The Adventure Begins
     #[default = true]
     level_1_1: bool,
@@ -159,7 +929,7 @@ struct Settings {
     /// 2-B2 - Demon Itsy's Ice Palace
     #[default = true]
     level_2_b2: bool,
-    /// 1-S2 - Ice Bridge to Eternity
+    /// 2-S2 - Ice Bridge to Eternity
     #[default = true]
     level_2_s2: bool,
     /// 3-1 - Lights, Camel, Action!
@@ -237,90 +1007,747 @@ struct Settings {
     /// 5-B1 - Secret Sentinel
     #[default = true]
     level_5_b1: bool,
-}
-
-struct Memory {
-    level_id: Address,
-    game_status: Address,
-    level_completion_flag: Address,
-}
-
-impl Memory {
-    async fn init(process: &Process, main_module_name: &str) -> Self {
-        let main_module_base = retry(|| process.get_module_address(main_module_name)).await;
-        let main_module_size = retry(|| pe::read_size_of_image(process, main_module_base)).await;
-        let main_module = (main_module_base, main_module_size as u64);
-
-        const LEVEL_ID: Signature<13> = Signature::new("0F 85 ?? ?? ?? ?? 8B 05 ?? ?? ?? ?? B9");
-        let level_id = retry(|| {
-            LEVEL_ID
-                .scan_process_range(process, main_module)
-                .map(|val| val + 8)
-                .and_then(|addr: Address| Some(addr + 0x4 + process.read::<i32>(addr).ok()?))
-        })
-        .await;
-
-        const GAME_STATUS: Signature<13> = Signature::new("89 05 ?? ?? ?? ?? 83 0D ?? ?? ?? ?? 01");
-        let game_status = retry(|| {
-            GAME_STATUS
-                .scan_process_range(process, main_module)
-                .map(|val| val + 2)
-                .and_then(|addr: Address| Some(addr + 0x4 + process.read::<i32>(addr).ok()?))
-        })
-        .await;
-
-        const LEVEL_COMPLETE_SCREEN: Signature<12> =
-            Signature::new("48 83 EC ?? C6 05 ?? ?? ?? ?? 01 C6");
-        let level_completion_flag: Address = retry(|| {
-            LEVEL_COMPLETE_SCREEN
-                .scan_process_range(process, main_module)
-                .map(|val| val + 6)
-                .and_then(|addr: Address| Some(addr + 0x5 + process.read::<i32>(addr).ok()?))
-        })
-        .await
-            + 1;
-
-        Self {
-            level_id,
-            game_status,
-            level_completion_flag,
-        }
-    }
-}
-
-#[derive(Default)]
-struct Watchers {
-    level: Watcher<Level>,
-    level_complete_flag: Watcher<bool>,
-    game_status: Watcher<GameStatus>,
-}
-
-#[allow(non_camel_case_types)]
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-enum Level {
-    L1_1,
-    L1_2,
-    L1_3,
-    L1_B1,
-    L1_S1,
-    L1_4,
-    L1_5,
-    L1_6,
-    L1_B2,
-    L1_S2,
-    L2_1,
-    L2_2,
-    L2_3,
-    L2_B1,
-    L2_S1,
-    L2_4,
-    L2_5,
-    L2_6,
-    L2_B2,
-    L2_S2,
-    L3_1,
-    L3_2,
-    L3_3,
+    /// Transit levels (never split with the preset categories, with Custom uncheck them above instead)
+    _skip: Title,
+    /// 1-1 - And So The Adventure Begins
+    #[default = false]
+    skip_1_1: bool,
+    /// 1-2 - Underground Overground
+    #[default = false]
+    skip_1_2: bool,
+    /// 1-3 - Shoutin Lava Lava Lava
+    #[default = false]
+    skip_1_3: bool,
+    /// 1-B1 - Lair of the Feeble
+    #[default = false]
+    skip_1_b1: bool,
+    /// 1-S1 - The Curvy Caverns
+    #[default = false]
+    skip_1_s1: bool,
+    /// 1-4 - The Tumbling Dantini
+    #[default = false]
+    skip_1_4: bool,
+    /// 1-5 - Cave Fear
+    #[default = false]
+    skip_1_5: bool,
+    /// 1-6 - Darkness Descends
+    #[default = false]
+    skip_1_6: bool,
+    /// 1-B2 - Fight Night with Flibby
+    #[default = false]
+    skip_1_b2: bool,
+    /// 1-S2 - The Twisty Tunnels
+    #[default = false]
+    skip_1_s2: bool,
+    /// 2-1 - The Ice of Life
+    #[default = false]
+    skip_2_1: bool,
+    /// 2-2 - Be Wheely Careful
+    #[default = false]
+    skip_2_2: bool,
+    /// 2-3 - Riot Brrrrr
+    #[default = false]
+    skip_2_3: bool,
+    /// 2-B1 - Chumly's Snow Den
+    #[default = false]
+    skip_2_b1: bool,
+    /// 2-S1 - Clouds of Ice
+    #[default = false]
+    skip_2_s1: bool,
+    /// 2-4 - I Snow Him So Well
+    #[default = false]
+    skip_2_4: bool,
+    /// 2-5 - Say No Snow
+    #[default = false]
+    skip_2_5: bool,
+    /// 2-6 - Licence to Chill
+    #[default = false]
+    skip_2_6: bool,
+    /// 2-B2 - Demon Itsy's Ice Palace
+    #[default = false]
+    skip_2_b2: bool,
+    /// 2-S2 - Ice Bridge to Eternity
+    #[default = false]
+    skip_2_s2: bool,
+    /// 3-1 - Lights, Camel, Action!
+    #[default = false]
+    skip_3_1: bool,
+    /// 3-2 - Mud Pit Mania
+    #[default = false]
+    skip_3_2: bool,
+    /// 3-3 - Goin' Underground
+    #[default = false]
+    skip_3_3: bool,
+    /// 3-B1 - The Deadly Tank of Neptuna
+    #[default = false]
+    skip_3_b1: bool,
+    /// 3-S1 - Arabian Heights
+    #[default = false]
+    skip_3_s1: bool,
+    /// 3-4 - Sand and Freedom
+    #[default = false]
+    skip_3_4: bool,
+    /// 3-5 - Leap of Faith
+    #[default = false]
+    skip_3_5: bool,
+    /// 3-6 - Life's a Beach
+    #[default = false]
+    skip_3_6: bool,
+    /// 3-B2 - Cactus Jack's Ranch
+    #[default = false]
+    skip_3_b2: bool,
+    /// 3-S2 - Defeato Burrito
+    #[default = false]
+    skip_3_s2: bool,
+    /// 4-1 - The Tower of Power
+    #[default = false]
+    skip_4_1: bool,
+    /// 4-2 - Hassle in the Castle
+    #[default = false]
+    skip_4_2: bool,
+    /// 4-3 - Dungeon of Defright
+    #[default = false]
+    skip_4_3: bool,
+    /// 4-B1 - Fosley's Freaky Donut
+    #[default = false]
+    skip_4_b1: bool,
+    /// 4-S1 - Smash and See
+    #[default = false]
+    skip_4_s1: bool,
+    /// 4-4 - Ballistic Meg's Fairway
+    #[default = false]
+    skip_4_4: bool,
+    /// 4-5 - Swipe Swiftly's Wicked Ride
+    #[default = false]
+    skip_4_5: bool,
+    /// 4-6 - Panic at Platform Pete's Lair
+    #[default = false]
+    skip_4_6: bool,
+    /// 4-B2 - Baron Dante's Funky Inferno
+    #[default = false]
+    skip_4_b2: bool,
+    /// 4-S2 - Jailhouse Croc
+    #[default = false]
+    skip_4_s2: bool,
+    /// 5-1 - And So The Adventure Returns
+    #[default = false]
+    skip_5_1: bool,
+    /// 5-2 - Diet Brrrrrrr
+    #[default = false]
+    skip_5_2: bool,
+    /// 5-3 - Trial on the Nile
+    #[default = false]
+    skip_5_3: bool,
+    /// 5-4 - Crox Interactive
+    #[default = false]
+    skip_5_4: bool,
+    /// 5-B1 - Secret Sentinel
+    #[default = false]
+    skip_5_b1: bool,
+    /// Delayed completion splits
+    _delay: Title,
+    /// Delay the completion split of the levels selected below (for levels raising the flag ahead of their actual end)
+    #[default = false]
+    delay_completion_split: bool,
+    /// Completion split delay
+    completion_split_delay: ShortDuration,
+    /// 1-1 - And So The Adventure Begins
+    #[default = false]
+    delay_1_1: bool,
+    /// 1-2 - Underground Overground
+    #[default = false]
+    delay_1_2: bool,
+    /// 1-3 - Shoutin Lava Lava Lava
+    #[default = false]
+    delay_1_3: bool,
+    /// 1-B1 - Lair of the Feeble
+    #[default = false]
+    delay_1_b1: bool,
+    /// 1-S1 - The Curvy Caverns
+    #[default = false]
+    delay_1_s1: bool,
+    /// 1-4 - The Tumbling Dantini
+    #[default = false]
+    delay_1_4: bool,
+    /// 1-5 - Cave Fear
+    #[default = false]
+    delay_1_5: bool,
+    /// 1-6 - Darkness Descends
+    #[default = false]
+    delay_1_6: bool,
+    /// 1-B2 - Fight Night with Flibby
+    #[default = false]
+    delay_1_b2: bool,
+    /// 1-S2 - The Twisty Tunnels
+    #[default = false]
+    delay_1_s2: bool,
+    /// 2-1 - The Ice of Life
+    #[default = false]
+    delay_2_1: bool,
+    /// 2-2 - Be Wheely Careful
+    #[default = false]
+    delay_2_2: bool,
+    /// 2-3 - Riot Brrrrr
+    #[default = false]
+    delay_2_3: bool,
+    /// 2-B1 - Chumly's Snow Den
+    #[default = false]
+    delay_2_b1: bool,
+    /// 2-S1 - Clouds of Ice
+    #[default = false]
+    delay_2_s1: bool,
+    /// 2-4 - I Snow Him So Well
+    #[default = false]
+    delay_2_4: bool,
+    /// 2-5 - Say No Snow
+    #[default = false]
+    delay_2_5: bool,
+    /// 2-6 - Licence to Chill
+    #[default = false]
+    delay_2_6: bool,
+    /// 2-B2 - Demon Itsy's Ice Palace
+    #[default = false]
+    delay_2_b2: bool,
+    /// 2-S2 - Ice Bridge to Eternity
+    #[default = false]
+    delay_2_s2: bool,
+    /// 3-1 - Lights, Camel, Action!
+    #[default = false]
+    delay_3_1: bool,
+    /// 3-2 - Mud Pit Mania
+    #[default = false]
+    delay_3_2: bool,
+    /// 3-3 - Goin' Underground
+    #[default = false]
+    delay_3_3: bool,
+    /// 3-B1 - The Deadly Tank of Neptuna
+    #[default = false]
+    delay_3_b1: bool,
+    /// 3-S1 - Arabian Heights
+    #[default = false]
+    delay_3_s1: bool,
+    /// 3-4 - Sand and Freedom
+    #[default = false]
+    delay_3_4: bool,
+    /// 3-5 - Leap of Faith
+    #[default = false]
+    delay_3_5: bool,
+    /// 3-6 - Life's a Beach
+    #[default = false]
+    delay_3_6: bool,
+    /// 3-B2 - Cactus Jack's Ranch
+    #[default = false]
+    delay_3_b2: bool,
+    /// 3-S2 - Defeato Burrito
+    #[default = false]
+    delay_3_s2: bool,
+    /// 4-1 - The Tower of Power
+    #[default = false]
+    delay_4_1: bool,
+    /// 4-2 - Hassle in the Castle
+    #[default = false]
+    delay_4_2: bool,
+    /// 4-3 - Dungeon of Defright
+    #[default = false]
+    delay_4_3: bool,
+    /// 4-B1 - Fosley's Freaky Donut
+    #[default = false]
+    delay_4_b1: bool,
+    /// 4-S1 - Smash and See
+    #[default = false]
+    delay_4_s1: bool,
+    /// 4-4 - Ballistic Meg's Fairway
+    #[default = false]
+    delay_4_4: bool,
+    /// 4-5 - Swipe Swiftly's Wicked Ride
+    #[default = false]
+    delay_4_5: bool,
+    /// 4-6 - Panic at Platform Pete's Lair
+    #[default = false]
+    delay_4_6: bool,
+    /// 4-B2 - Baron Dante's Funky Inferno
+    #[default = false]
+    delay_4_b2: bool,
+    /// 4-S2 - Jailhouse Croc
+    #[default = false]
+    delay_4_s2: bool,
+    /// 5-1 - And So The Adventure Returns
+    #[default = false]
+    delay_5_1: bool,
+    /// 5-2 - Diet Brrrrrrr
+    #[default = false]
+    delay_5_2: bool,
+    /// 5-3 - Trial on the Nile
+    #[default = false]
+    delay_5_3: bool,
+    /// 5-4 - Crox Interactive
+    #[default = false]
+    delay_5_4: bool,
+    /// 5-B1 - Secret Sentinel
+    #[default = false]
+    delay_5_b1: bool,
+    /// Level bests
+    _level_bests: Title,
+    /// Keep track of the best time for each level across sessions
+    #[default = false]
+    track_level_bests: bool,
+    /// Clear the stored level bests
+    #[default = false]
+    clear_level_bests: bool,
+    /// Debugging
+    _debugging: Title,
+    /// Verbose logging
+    #[default = false]
+    verbose_logging: bool,
+    /// Dry run: log start, split, reset and load events instead of controlling the timer
+    #[default = false]
+    dry_run: bool,
+}
+
+/// Brings settings saved by an older version of the splitter up to date before they get registered.
+///
+/// New settings simply fall back to their default value and settings that no longer exist are
+/// ignored when registering, so only renamed settings need their stored value carried over.
+fn migrate_settings() {
+    const VERSION_KEY: &str = "settings_version";
+
+    let map = settings::Map::load();
+    let version = map
+        .get(VERSION_KEY)
+        .and_then(|val| val.get_i64())
+        .unwrap_or_default();
+
+    if version >= SETTINGS_VERSION {
+        return;
+    }
+
+    for &(since, old, new) in RENAMED_SETTINGS {
+        if version < since && map.get(new).is_none() {
+            if let Some(val) = map.get(old) {
+                map.insert(new, &val);
+            }
+        }
+    }
+
+    map.insert(VERSION_KEY, &SETTINGS_VERSION.into());
+    map.store();
+}
+
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum ShortDuration {
+    /// 0.5 seconds
+    HalfSecond,
+    /// 1 second
+    #[default]
+    OneSecond,
+    /// 2 seconds
+    TwoSeconds,
+    /// 3 seconds
+    ThreeSeconds,
+    /// 5 seconds
+    FiveSeconds,
+    /// 10 seconds
+    TenSeconds,
+}
+
+impl From<ShortDuration> for Duration {
+    fn from(value: ShortDuration) -> Self {
+        match value {
+            ShortDuration::HalfSecond => Duration::milliseconds(500),
+            ShortDuration::OneSecond => Duration::seconds(1),
+            ShortDuration::TwoSeconds => Duration::seconds(2),
+            ShortDuration::ThreeSeconds => Duration::seconds(3),
+            ShortDuration::FiveSeconds => Duration::seconds(5),
+            ShortDuration::TenSeconds => Duration::seconds(10),
+        }
+    }
+}
+
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum Category {
+    /// All levels
+    AllLevels,
+    /// All levels except secret levels
+    NoSecretLevels,
+    /// Custom (use the level settings below)
+    #[default]
+    Custom,
+}
+
+// The variant names are stored in the settings, so they're kept readable on their own
+#[allow(clippy::enum_variant_names)]
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum SplitGrouping {
+    /// Split on every level
+    #[default]
+    EveryLevel,
+    /// Split every 2 levels
+    EveryTwoLevels,
+    /// Split every 3 levels
+    EveryThreeLevels,
+    /// Split every 5 levels
+    EveryFiveLevels,
+}
+
+impl SplitGrouping {
+    /// Number of level completions making up a split
+    const fn size(self) -> u8 {
+        match self {
+            Self::EveryLevel => 1,
+            Self::EveryTwoLevels => 2,
+            Self::EveryThreeLevels => 3,
+            Self::EveryFiveLevels => 5,
+        }
+    }
+}
+
+#[derive(Gui, Copy, Clone, PartialEq, Eq)]
+enum LevelRegression {
+    /// Ignore it
+    #[default]
+    Ignore,
+    /// Log a warning
+    Warn,
+    /// Reset the run
+    Reset,
+}
+
+struct Memory {
+    /// Size of the main module. It differs between builds of the game, so it tells them apart
+    image_size: u32,
+    byte_order: ByteOrder,
+    level_id: Address,
+    game_status: Address,
+    level_completion_flag: Address,
+    /// How the signatures were resolved
+    signature_set: SignatureSet,
+}
+
+impl Memory {
+    const LEVEL_ID: Signature<13> = Signature::new("0F 85 ?? ?? ?? ?? 8B 05 ?? ?? ?? ?? B9");
+    const GAME_STATUS: Signature<13> = Signature::new("89 05 ?? ?? ?? ?? 83 0D ?? ?? ?? ?? 01");
+    const LEVEL_COMPLETE_SCREEN: Signature<12> =
+        Signature::new("48 83 EC ?? C6 05 ?? ?? ?? ?? 01 C6");
+
+    async fn init(process: &Process, main_module_name: &str) -> Self {
+        let main_module_base = retry(|| process.get_module_address(main_module_name)).await;
+        let main_module_size = retry(|| pe::read_size_of_image(process, main_module_base)).await;
+        let main_module = MainModule::new(process, main_module_base, main_module_size);
+
+        let level_id = retry(|| {
+            main_module
+                .scan(process, &Self::LEVEL_ID)
+                .map(|val| val + 8)
+                .and_then(|addr: Address| Some(addr + 0x4 + process.read::<i32>(addr).ok()?))
+        })
+        .await;
+
+        let game_status = retry(|| {
+            main_module
+                .scan(process, &Self::GAME_STATUS)
+                .map(|val| val + 2)
+                .and_then(|addr: Address| Some(addr + 0x4 + process.read::<i32>(addr).ok()?))
+        })
+        .await;
+
+        let level_completion_flag: Address = retry(|| {
+            main_module
+                .scan(process, &Self::LEVEL_COMPLETE_SCREEN)
+                .map(|val| val + 6)
+                .and_then(|addr: Address| Some(addr + 0x5 + process.read::<i32>(addr).ok()?))
+        })
+        .await
+            + 1;
+
+        // All known builds are little endian, but a port storing values the other way around
+        // would show up as a level ID that only makes sense once its bytes are swapped
+        let byte_order = match process.read::<u32>(level_id) {
+            Ok(val) if val > MAX_LEVEL_ID && val.swap_bytes() <= MAX_LEVEL_ID => ByteOrder::Big,
+            _ => ByteOrder::Little,
+        };
+
+        Self {
+            image_size: main_module_size,
+            byte_order,
+            level_id,
+            game_status,
+            level_completion_flag,
+            signature_set: main_module.signature_set.get(),
+        }
+    }
+
+    /// Reads the game status and the level ID, returning them if they look like what the game
+    /// actually stores there: a game status the splitter knows, and the level ID of either a level
+    /// or one of the sentinels held outside of levels (see [`level_from_id`]). Anything else
+    /// means a signature resolved to unrelated data, or the game is still booting
+    fn self_test(&self, process: &Process) -> Option<(u32, u32)> {
+        let game_status = self.read_u32(process, self.game_status).ok()?;
+        let level_id = self.read_u32(process, self.level_id).ok()?;
+        (GameStatus::from_raw(game_status).ne(&GameStatus::Unknown)
+            && level_id as i32 <= MAX_LEVEL_ID as i32)
+            .then_some((game_status, level_id))
+    }
+
+    /// The signature matches a byte store (`mov byte ptr`), so the flag is a single byte
+    fn read_level_completion_flag(&self, process: &Process) -> Result<u32, Error> {
+        process
+            .read::<u8>(self.level_completion_flag)
+            .map(u32::from)
+    }
+
+    fn read_u32(&self, process: &Process, address: Address) -> Result<u32, Error> {
+        let val = process.read::<u32>(address)?;
+        Ok(match self.byte_order {
+            ByteOrder::Little => u32::from_le(val),
+            ByteOrder::Big => u32::from_be(val),
+        })
+    }
+}
+
+/// Memory ranges of the game's main module to scan for signatures
+struct MainModule {
+    image: (Address, u64),
+    code: Option<(Address, u64)>,
+    /// Least preferred way any signature had to be resolved so far
+    signature_set: Cell<SignatureSet>,
+}
+
+impl MainModule {
+    fn new(process: &Process, base: Address, size_of_image: u32) -> Self {
+        Self {
+            image: (base, size_of_image as u64),
+            code: Self::code_section(process, base),
+            signature_set: Cell::new(SignatureSet::CodeSection),
+        }
+    }
+
+    /// The code section is located through the PE headers: e_lfanew points to the NT
+    /// headers, whose optional header holds SizeOfCode (+0x4) and BaseOfCode (+0x14)
+    fn code_section(process: &Process, base: Address) -> Option<(Address, u64)> {
+        let nt_headers = base + process.read::<u32>(base + 0x3C).ok()? as u64;
+        let optional_header = nt_headers + 0x18;
+        let size_of_code = process.read::<u32>(optional_header + 0x4).ok()?;
+        let base_of_code = process.read::<u32>(optional_header + 0x14).ok()?;
+        Some((base + base_of_code as u64, size_of_code as u64))
+    }
+
+    /// All the signatures point to code, so the code section is scanned first as it's a lot
+    /// smaller than the whole image, making the attach faster. The whole image is still
+    /// scanned as a fallback, at the cost of a second, slower scan when the first one misses
+    fn scan<const N: usize>(&self, process: &Process, signature: &Signature<N>) -> Option<Address> {
+        self.code
+            .and_then(|code| signature.scan_process_range(process, code))
+            .or_else(|| {
+                let addr = signature.scan_process_range(process, self.image)?;
+                self.widen_signature_set(SignatureSet::FullImage);
+                Some(addr)
+            })
+    }
+
+    /// The set reported is the least preferred one any signature needed
+    fn widen_signature_set(&self, signature_set: SignatureSet) {
+        self.signature_set
+            .set(self.signature_set.get().max(signature_set));
+    }
+}
+
+/// Ordered from the most to the least preferred way of resolving the signatures
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SignatureSet {
+    CodeSection,
+    FullImage,
+}
+
+impl SignatureSet {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::CodeSection => "Code section",
+            Self::FullImage => "Full image",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ByteOrder {
+    Little,
+    Big,
+}
+
+/// Kinds of game status changes that can be paused over, see [`is_loading`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Transition {
+    /// From the world map into a level
+    LevelEntry,
+    /// From a level back to the world map
+    LevelExit,
+    /// To or from the main menu
+    Menu,
+}
+
+impl Transition {
+    const fn classify(old: GameStatus, current: GameStatus) -> Option<Self> {
+        match (old, current) {
+            (GameStatus::WorldMap, GameStatus::InGame) => Some(Self::LevelEntry),
+            (GameStatus::InGame, GameStatus::WorldMap) => Some(Self::LevelExit),
+            (GameStatus::MainMenu, GameStatus::WorldMap | GameStatus::InGame)
+            | (GameStatus::WorldMap | GameStatus::InGame, GameStatus::MainMenu) => Some(Self::Menu),
+            _ => None,
+        }
+    }
+
+    /// How long game time stays paused after the transition, if enabled
+    fn pause(self, settings: &Settings) -> Option<ShortDuration> {
+        match self {
+            Self::LevelEntry => settings
+                .pause_on_level_load
+                .then_some(settings.level_load_pause),
+            Self::LevelExit => settings
+                .pause_on_level_exit
+                .then_some(settings.level_exit_pause),
+            Self::Menu => settings
+                .pause_on_menu_transition
+                .then_some(settings.menu_transition_pause),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum LevelExit {
+    Completed,
+    /// Left through the pause menu without completing the level
+    BackedOut,
+}
+
+impl LevelExit {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Completed => "Completed",
+            Self::BackedOut => "Backed out",
+        }
+    }
+}
+
+#[derive(Default)]
+struct Watchers {
+    /// Level being played. `None` outside of levels (eg. menus)
+    level: Watcher<Option<Level>>,
+    level_complete_flag: Watcher<bool>,
+    /// Raw state of the completion flag on the previous tick, see [`debounce_default`]
+    last_flag_read: Option<bool>,
+    /// Value of the completion flag when no level has been completed. Assumed 0 until calibrated
+    level_completion_idle: u32,
+    /// Raw value the completion flag has held since the level load, while its idle value is
+    /// learned. `Some(None)` once it read 0 or changed value
+    idle_candidate: Option<Option<u32>>,
+    on_results_screen: bool,
+    /// Last level completed (see [`level_completed`]), until the next level is entered
+    last_completed_level: Option<Level>,
+    /// Last level completed along with the time elapsed since. Unlike `last_completed_level`,
+    /// it's kept over the next level load, so a delayed split still goes through after it
+    last_completion: Option<(Level, Duration)>,
+    /// How the last level was left for the world map. Kept until the next level is left, so
+    /// entering a level again after a back-out can be told apart
+    level_exit: Option<LevelExit>,
+    game_status: Watcher<GameStatus>,
+    raw_level_id: Option<u32>,
+    /// Level read on the previous tick, see [`debounce_default`]
+    last_level_read: Option<Option<Level>>,
+    valid_reads: u8,
+    unknown_status_time: Duration,
+    /// Last transition between the hub, the levels and the menus, with the time elapsed since
+    last_transition: Option<(Transition, Duration)>,
+    time_since_level_load: Option<Duration>,
+    /// Time elapsed since the splitter last split, until the next level load
+    time_since_split: Option<Duration>,
+}
+
+/// Statistics accumulated over the course of a run
+struct RunState {
+    load_time: Duration,
+    /// Game time elapsed since the run started
+    game_time: Duration,
+    /// Game time elapsed since the last split
+    segment_time: Duration,
+    /// Game time elapsed since the current level was entered
+    level_game_time: Duration,
+    /// Game time spent in each completed level, indexed by [`Level::ordinal`]
+    level_times: [Duration; 45],
+    /// Level completions since the last split, when splits are grouped
+    grouped_completions: u8,
+    /// The startup segment is still going on and has to be split off on entering a level
+    start_split_pending: bool,
+    /// Index of the next level to split on in the custom split sequence
+    sequence_position: usize,
+    /// The final split of the run has been issued
+    run_finished: bool,
+    /// Furthest level completed in the run, see [`Level::ordinal`]
+    furthest_level: Option<Level>,
+}
+
+impl Default for RunState {
+    fn default() -> Self {
+        Self {
+            load_time: Duration::ZERO,
+            game_time: Duration::ZERO,
+            segment_time: Duration::ZERO,
+            level_game_time: Duration::ZERO,
+            level_times: [Duration::ZERO; 45],
+            grouped_completions: 0,
+            start_split_pending: false,
+            sequence_position: 0,
+            run_finished: false,
+            furthest_level: None,
+        }
+    }
+}
+
+impl RunState {
+    /// Clears the timing of the previous run. Unless `full` is set, the statistics it
+    /// accumulated are kept, so they can carry over into the next attempt
+    fn reset(&mut self, full: bool) {
+        match full {
+            true => *self = Self::default(),
+            false => {
+                self.game_time = Duration::ZERO;
+                self.segment_time = Duration::ZERO;
+                self.level_game_time = Duration::ZERO;
+                self.level_times = [Duration::ZERO; 45];
+                self.grouped_completions = 0;
+                self.start_split_pending = false;
+                self.sequence_position = 0;
+                self.run_finished = false;
+                self.furthest_level = None;
+            }
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+enum Level {
+    L1_1,
+    L1_2,
+    L1_3,
+    L1_B1,
+    L1_S1,
+    L1_4,
+    L1_5,
+    L1_6,
+    L1_B2,
+    L1_S2,
+    L2_1,
+    L2_2,
+    L2_3,
+    L2_B1,
+    L2_S1,
+    L2_4,
+    L2_5,
+    L2_6,
+    L2_B2,
+    L2_S2,
+    L3_1,
+    L3_2,
+    L3_3,
     L3_B1,
     L3_S1,
     L3_4,
@@ -345,168 +1772,2064 @@ enum Level {
     L5_B1,
 }
 
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-enum GameStatus {
-    Intro,
-    DemoMode,
-    MainMenu,
-    WorldMap,
-    InGame,
-    Unknown,
+/// Every level, in declaration order (see [`Level::ordinal`])
+const LEVELS: [Level; 45] = [
+    Level::L1_1,
+    Level::L1_2,
+    Level::L1_3,
+    Level::L1_B1,
+    Level::L1_S1,
+    Level::L1_4,
+    Level::L1_5,
+    Level::L1_6,
+    Level::L1_B2,
+    Level::L1_S2,
+    Level::L2_1,
+    Level::L2_2,
+    Level::L2_3,
+    Level::L2_B1,
+    Level::L2_S1,
+    Level::L2_4,
+    Level::L2_5,
+    Level::L2_6,
+    Level::L2_B2,
+    Level::L2_S2,
+    Level::L3_1,
+    Level::L3_2,
+    Level::L3_3,
+    Level::L3_B1,
+    Level::L3_S1,
+    Level::L3_4,
+    Level::L3_5,
+    Level::L3_6,
+    Level::L3_B2,
+    Level::L3_S2,
+    Level::L4_1,
+    Level::L4_2,
+    Level::L4_3,
+    Level::L4_B1,
+    Level::L4_S1,
+    Level::L4_4,
+    Level::L4_5,
+    Level::L4_6,
+    Level::L4_B2,
+    Level::L4_S2,
+    Level::L5_1,
+    Level::L5_2,
+    Level::L5_3,
+    Level::L5_4,
+    Level::L5_B1,
+];
+
+// Every level must be listed exactly once, at its ordinal
+const _: () = {
+    let mut i = 0;
+    while i < LEVELS.len() {
+        assert!(LEVELS[i].ordinal() == i, "LEVELS is out of order");
+        i += 1;
+    }
+};
+
+impl Level {
+    const fn is_boss(self) -> bool {
+        matches!(
+            self,
+            Self::L1_B1
+                | Self::L1_B2
+                | Self::L2_B1
+                | Self::L2_B2
+                | Self::L3_B1
+                | Self::L3_B2
+                | Self::L4_B1
+                | Self::L4_B2
+                | Self::L5_B1
+        )
+    }
+
+    /// Every level leads back to the world map once completed, except for the final boss which ends the game
+    const fn returns_to_world_map(self) -> bool {
+        !matches!(self, Self::L5_B1)
+    }
+
+    /// Position of the level in the progression through the game, as the levels are declared
+    /// island by island, in the order the world map lays them out
+    const fn ordinal(self) -> usize {
+        self as usize
+    }
+
+    const fn is_secret(self) -> bool {
+        matches!(
+            self,
+            Self::L1_S1
+                | Self::L1_S2
+                | Self::L2_S1
+                | Self::L2_S2
+                | Self::L3_S1
+                | Self::L3_S2
+                | Self::L4_S1
+                | Self::L4_S2
+        )
+    }
+
+    const fn code(self) -> &'static str {
+        match self {
+            Self::L1_1 => "1-1",
+            Self::L1_2 => "1-2",
+            Self::L1_3 => "1-3",
+            Self::L1_B1 => "1-B1",
+            Self::L1_S1 => "1-S1",
+            Self::L1_4 => "1-4",
+            Self::L1_5 => "1-5",
+            Self::L1_6 => "1-6",
+            Self::L1_B2 => "1-B2",
+            Self::L1_S2 => "1-S2",
+            Self::L2_1 => "2-1",
+            Self::L2_2 => "2-2",
+            Self::L2_3 => "2-3",
+            Self::L2_B1 => "2-B1",
+            Self::L2_S1 => "2-S1",
+            Self::L2_4 => "2-4",
+            Self::L2_5 => "2-5",
+            Self::L2_6 => "2-6",
+            Self::L2_B2 => "2-B2",
+            Self::L2_S2 => "2-S2",
+            Self::L3_1 => "3-1",
+            Self::L3_2 => "3-2",
+            Self::L3_3 => "3-3",
+            Self::L3_B1 => "3-B1",
+            Self::L3_S1 => "3-S1",
+            Self::L3_4 => "3-4",
+            Self::L3_5 => "3-5",
+            Self::L3_6 => "3-6",
+            Self::L3_B2 => "3-B2",
+            Self::L3_S2 => "3-S2",
+            Self::L4_1 => "4-1",
+            Self::L4_2 => "4-2",
+            Self::L4_3 => "4-3",
+            Self::L4_B1 => "4-B1",
+            Self::L4_S1 => "4-S1",
+            Self::L4_4 => "4-4",
+            Self::L4_5 => "4-5",
+            Self::L4_6 => "4-6",
+            Self::L4_B2 => "4-B2",
+            Self::L4_S2 => "4-S2",
+            Self::L5_1 => "5-1",
+            Self::L5_2 => "5-2",
+            Self::L5_3 => "5-3",
+            Self::L5_4 => "5-4",
+            Self::L5_B1 => "5-B1",
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+enum GameStatus {
+    Intro,
+    DemoMode,
+    MainMenu,
+    WorldMap,
+    InGame,
+    Unknown,
+}
+
+impl GameStatus {
+    const fn from_raw(raw: u32) -> Self {
+        match raw {
+            2 => Self::DemoMode,
+            3 => Self::MainMenu,
+            5 => Self::InGame,
+            8 => Self::WorldMap,
+            12 => Self::Intro,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+fn tick_duration() -> Duration {
+    Duration::seconds_f64(TICK_RATE.recip())
+}
+
+/// Settings key under which the best time of a level is stored
+fn level_best_key(level: Level) -> ArrayString<32> {
+    let mut key = ArrayString::new();
+    let _ = write!(key, "level_best_{}", level.code());
+    key
+}
+
+/// Compares the time spent in a level against its stored best, replacing it if faster and
+/// `store` is set. Returns whether the best has been beaten
+fn record_level_best(level: Level, time: Duration, store: bool) -> bool {
+    let key = level_best_key(level);
+    let map = settings::Map::load();
+
+    // Bests are stored in seconds, with 0 meaning none has been set yet
+    let beaten = map
+        .get(&key)
+        .and_then(|val| val.get_f64())
+        .filter(|&best| best > 0.0)
+        .is_none_or(|best| time.as_seconds_f64() < best);
+
+    if beaten && store {
+        map.insert(&key, &time.as_seconds_f64().into());
+        map.store();
+    }
+
+    beaten
+}
+
+/// Wipes the stored level bests, then unticks the setting that asked for it
+fn clear_level_bests() {
+    let map = settings::Map::load();
+    for level in LEVELS {
+        map.insert(&level_best_key(level), &0.0.into());
+    }
+    map.insert("clear_level_bests", &false.into());
+    map.store();
+}
+
+/// Raw values read from the game on a tick. `None` where the read failed
+#[derive(Copy, Clone, Debug)]
+struct Reads {
+    game_status: Option<u32>,
+    level_completion_flag: Option<u32>,
+    level_id: Option<u32>,
+}
+
+fn update_loop(process: &Process, memory: &Memory, watchers: &mut Watchers) {
+    let was_stalled = watchers.unknown_status_time >= STALL_THRESHOLD;
+
+    apply_reads(
+        watchers,
+        Reads {
+            game_status: memory.read_u32(process, memory.game_status).ok(),
+            level_completion_flag: memory.read_level_completion_flag(process).ok(),
+            level_id: memory.read_u32(process, memory.level_id).ok(),
+        },
+    );
+
+    if !was_stalled && watchers.unknown_status_time >= STALL_THRESHOLD {
+        asr::print_message(
+            "Warning: the game status has been unrecognized for a while, the game might be stuck",
+        );
+    }
+}
+
+/// Updates the watchers from what was read on the tick. Kept apart from the memory reads
+/// themselves, so recorded values can be replayed through it
+fn apply_reads(watchers: &mut Watchers, reads: Reads) {
+    let game_status = reads.game_status;
+
+    watchers
+        .game_status
+        .update_infallible(game_status.map_or(GameStatus::Unknown, GameStatus::from_raw));
+
+    // The game can end up stuck in an unrecognized state (eg. a softlock) while the process stays alive
+    if watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.current.eq(&GameStatus::Unknown))
+    {
+        watchers.unknown_status_time += tick_duration();
+    } else {
+        watchers.unknown_status_time = Duration::ZERO;
+    }
+
+    match watchers
+        .game_status
+        .pair
+        .and_then(|val| Transition::classify(val.old, val.current))
+    {
+        Some(transition) => watchers.last_transition = Some((transition, Duration::ZERO)),
+        None => {
+            if let Some((_, time)) = &mut watchers.last_transition {
+                *time += tick_duration();
+            }
+        }
+    }
+
+    // Going back to the attract intro ends the play session, so nothing about
+    // the last level should be carried over into the next one
+    if watchers.game_status.pair.is_some_and(|val| {
+        val.changed_from_to(&GameStatus::MainMenu, &GameStatus::Intro)
+            || val.changed_from_to(&GameStatus::WorldMap, &GameStatus::Intro)
+    }) {
+        watchers.level = Watcher::default();
+        watchers.time_since_level_load = None;
+    }
+
+    // Time elapsed since the last WorldMap -> InGame transition, used to pause game time during the level load
+    if watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.changed_from_to(&GameStatus::WorldMap, &GameStatus::InGame))
+    {
+        watchers.time_since_level_load = Some(Duration::ZERO);
+        watchers.idle_candidate = None;
+        // Any post-split pause still going on is handed over to the level load
+        watchers.time_since_split = None;
+        watchers.last_completed_level = None;
+    } else if let Some(time) = &mut watchers.time_since_level_load {
+        *time += tick_duration();
+    }
+
+    if let Some(time) = &mut watchers.time_since_split {
+        *time += tick_duration();
+    }
+
+    let level_completion_flag = reads.level_completion_flag;
+
+    // Builds don't necessarily agree on the value the flag holds outside of the results
+    // screen. It's learned over the first moments of each level, when its rises aren't trusted
+    // anyway (see [`level_completed`]). The flag can still be set from the previous level for a
+    // moment after entering the next one though, so another value than 0 is only taken if the
+    // flag held it steadily over that whole time. Anything else than the idle value is then
+    // considered a completion
+    match watchers.time_since_level_load {
+        Some(time) if time < MIN_LEVEL_TIME => {
+            if let Some(val) = level_completion_flag {
+                watchers.idle_candidate = Some(match watchers.idle_candidate {
+                    None => Some(val).filter(|&val| val != 0),
+                    Some(candidate) => candidate.filter(|&candidate| candidate == val),
+                });
+            }
+        }
+        Some(_) => {
+            if let Some(candidate) = watchers.idle_candidate.take() {
+                watchers.level_completion_idle = candidate.unwrap_or_default();
+            }
+        }
+        None => (),
+    }
+
+    let level_complete = debounce_default(
+        &mut watchers.last_flag_read,
+        level_completion_flag.is_some_and(|val| val != watchers.level_completion_idle),
+        false,
+        watchers.level_complete_flag.pair.map(|val| val.current),
+    );
+    watchers
+        .level_complete_flag
+        .update_infallible(level_complete);
+
+    // The flag falling back while still in the level means the completion got cancelled (eg.
+    // the player died on the results screen). Leaving the level afterwards is then a back-out
+    if watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.old.eq(&GameStatus::InGame) && val.current.eq(&GameStatus::InGame))
+        && watchers
+            .level_complete_flag
+            .pair
+            .is_some_and(|val| val.changed_from_to(&true, &false))
+    {
+        watchers.last_completed_level = None;
+        watchers.on_results_screen = false;
+    }
+
+    // Only completions split() would act on are tracked, so that a glitched rise of the
+    // flag can't be picked up later on by a delayed or deferred split
+    if level_completed(watchers) {
+        // The level watcher hasn't been updated yet this tick, so its current value
+        // is the one level_for_split will pick once it is
+        watchers.last_completed_level = watchers.level.pair.and_then(|val| val.current);
+        watchers.last_completion = watchers
+            .last_completed_level
+            .map(|level| (level, Duration::ZERO));
+    } else if let Some((_, time)) = &mut watchers.last_completion {
+        *time += tick_duration();
+    }
+
+    // From a level's completion until the world map is reached, the game is on its results
+    // screen. Like the completion itself, a rise of the flag right after entering a level isn't
+    // trusted, so a checkpoint doesn't stop game time for the rest of the level
+    if level_completed(watchers) {
+        watchers.on_results_screen = true;
+    } else if watchers
+        .game_status
+        .pair
+        .is_some_and(|val| !matches!(val.current, GameStatus::InGame | GameStatus::Unknown))
+    {
+        watchers.on_results_screen = false;
+    }
+
+    // A level is left for the world map either after its completion or by backing out of it.
+    // Only validated completions count, so backing out after a glitched rise of the flag (eg.
+    // within the first seconds of the level) is still recognized as such
+    if watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.changed_from_to(&GameStatus::InGame, &GameStatus::WorldMap))
+    {
+        watchers.level_exit = Some(match watchers.last_completed_level {
+            Some(_) => LevelExit::Completed,
+            None => LevelExit::BackedOut,
+        });
+    }
+
+    let level_id = reads.level_id;
+    watchers.raw_level_id = level_id;
+
+    watchers.valid_reads = match game_status.is_some() && level_id.is_some() {
+        true => watchers.valid_reads.saturating_add(1),
+        false => 0,
+    };
+
+    match level_id {
+        Some(id) => {
+            let level = debounce_default(
+                &mut watchers.last_level_read,
+                level_from_id(id as i32),
+                None,
+                watchers.level.pair.map(|val| val.current),
+            );
+            watchers.level.update_infallible(level);
+        }
+        // A failed read says nothing about the level, so the last known one is kept
+        None => {
+            if let Some(val) = watchers.level.pair {
+                watchers.level.update_infallible(val.current);
+            }
+        }
+    }
+}
+
+/// During the fade out after a level, both the level ID and the completion flag can read zero
+/// for a moment. A value falling back to its default is only accepted once it's been read on two
+/// ticks in a row, so the blip doesn't pass for leaving the level or for the flag being cleared
+fn debounce_default<T: Copy + PartialEq>(
+    last_read: &mut Option<T>,
+    read: T,
+    default: T,
+    accepted: Option<T>,
+) -> T {
+    let previous_read = last_read.replace(read);
+    match accepted {
+        Some(accepted) if read == default && previous_read != Some(default) => accepted,
+        _ => read,
+    }
+}
+
+/// Maps a raw level ID to its level. Outside of levels (eg. menus), the game holds a negative
+/// sentinel or an ID below 1-1's. IDs past [`MAX_LEVEL_ID`] aren't used by the game at all
+fn level_from_id(id: i32) -> Option<Level> {
+    Some(match id {
+        10 => Level::L1_1,
+        11 => Level::L1_2,
+        12 => Level::L1_3,
+        13 => Level::L1_B1,
+        14 => Level::L1_4,
+        15 => Level::L1_5,
+        16 => Level::L1_6,
+        17 => Level::L1_B2,
+        18 => Level::L1_S1,
+        19 => Level::L1_S2,
+        20 => Level::L2_1,
+        21 => Level::L2_2,
+        22 => Level::L2_3,
+        23 => Level::L2_B1,
+        24 => Level::L2_4,
+        25 => Level::L2_5,
+        26 => Level::L2_6,
+        27 => Level::L2_B2,
+        28 => Level::L2_S1,
+        29 => Level::L2_S2,
+        30 => Level::L3_1,
+        31 => Level::L3_2,
+        32 => Level::L3_3,
+        33 => Level::L3_B1,
+        34 => Level::L3_4,
+        35 => Level::L3_5,
+        36 => Level::L3_6,
+        37 => Level::L3_B2,
+        38 => Level::L3_S1,
+        39 => Level::L3_S2,
+        40 => Level::L4_1,
+        41 => Level::L4_2,
+        42 => Level::L4_3,
+        43 => Level::L4_B1,
+        44 => Level::L4_4,
+        45 => Level::L4_5,
+        46 => Level::L4_6,
+        47 => Level::L4_B2,
+        48 => Level::L4_S1,
+        49 => Level::L4_S2,
+        50 => Level::L5_1,
+        51 => Level::L5_2,
+        52 => Level::L5_3,
+        53 => Level::L5_4,
+        54 => Level::L5_B1,
+        // This includes IDs 1 to 9, below 1-1's, which no playable level uses. Whether they
+        // stand for the intro, the menus or something else is unconfirmed: the raw ID is
+        // published as a variable when verbose logging is enabled, to help map them
+        _ => return None,
+    })
+}
+
+/// Whether a level has just been completed. The completion flag alone isn't trusted: on fast
+/// transitions the status can already have left InGame on the same tick the flag rises, so being
+/// in game on either side of the tick is enough, but the level must have been played for a while
+fn level_completed(watchers: &Watchers) -> bool {
+    watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.old.eq(&GameStatus::InGame) || val.current.eq(&GameStatus::InGame))
+        && watchers
+            .level_complete_flag
+            .pair
+            .is_some_and(|val| val.changed_from_to(&false, &true))
+        // Spurious rises of the flag (eg. checkpoints) can happen right after entering a level
+        && watchers
+            .time_since_level_load
+            .is_none_or(|time| time >= MIN_LEVEL_TIME)
+}
+
+fn start(watchers: &Watchers, settings: &Settings) -> bool {
+    if !settings.start {
+        return false;
+    }
+
+    if settings.race_practice {
+        return watchers
+            .game_status
+            .pair
+            .is_some_and(|val| val.changed_from_to(&GameStatus::WorldMap, &GameStatus::InGame));
+    }
+
+    if settings.start_on_intro_end {
+        // Whether the logos are skipped or left to play out, the game leaves
+        // the intro towards either the main menu or the attract demo
+        return watchers.game_status.pair.is_some_and(|val| {
+            val.old.eq(&GameStatus::Intro)
+                && !matches!(val.current, GameStatus::Intro | GameStatus::Unknown)
+        });
+    }
+
+    watchers
+        .game_status
+        .pair
+        .is_some_and(|val| val.changed_from_to(&GameStatus::MainMenu, &GameStatus::WorldMap))
+        && watchers
+            .level
+            .pair
+            .is_some_and(|val| val.current.eq(&Some(Level::L1_1)))
+}
+
+fn is_loading(watchers: &Watchers, settings: &Settings) -> Option<bool> {
+    if !(settings.pause_on_level_load
+        || settings.pause_on_level_exit
+        || settings.pause_on_menu_transition
+        || settings.pause_on_level_entry
+        || settings.pause_on_stall
+        || settings.pause_outside_gameplay
+        || settings.pause_after_split
+        || settings.pause_on_results_screen
+        || settings.pause_during_intro)
+    {
+        return None;
+    }
+
+    // Only an approximation of load removal: any time spent outside of actual gameplay
+    // (menus, world map, cutscenes) is counted as dead time, loads or not
+    let outside_gameplay = settings.pause_outside_gameplay
+        && watchers
+            .game_status
+            .pair
+            .is_some_and(|val| val.current.ne(&GameStatus::InGame));
+
+    // For runs timed from boot, the logos and the attract demo are not interactive time. A run
+    // started as the intro ends can still go through the attract demo, which is paused over too
+    let intro = settings.pause_during_intro
+        && watchers
+            .game_status
+            .pair
+            .is_some_and(|val| matches!(val.current, GameStatus::Intro | GameStatus::DemoMode));
+
+    let stalled = settings.pause_on_stall && watchers.unknown_status_time >= STALL_THRESHOLD;
+
+    let within = |duration: ShortDuration| {
+        watchers
+            .time_since_level_load
+            .is_some_and(|time| time < duration.into())
+    };
+
+    // Each kind of transition has its own pause, starting as the status changes. Like every other
+    // condition here, it only ever adds paused time: had the game a loading flag of its own, a
+    // transition pause would just extend past the end of the load it overlaps with
+    let transition = watchers.last_transition.is_some_and(|(transition, time)| {
+        transition
+            .pause(settings)
+            .is_some_and(|duration| time < duration.into())
+    });
+
+    let level_entry = settings.pause_on_level_entry
+        && watchers
+            .level
+            .pair
+            .and_then(|val| val.current)
+            .is_some_and(|level| LevelSettings::of(level, settings).pause_on_entry)
+        && within(settings.level_entry_pause);
+
+    // The results screen following a split, either for a fixed duration or up to the next level load
+    let after_split = settings.pause_after_split
+        && watchers.time_since_split.is_some_and(|time| {
+            settings.post_split_pause_until_level_load || time < settings.post_split_pause.into()
+        });
+
+    // Game time stops at the completion itself, while real time keeps going until the world map
+    let results_screen = settings.pause_on_results_screen && watchers.on_results_screen;
+
+    Some(
+        intro
+            || outside_gameplay
+            || stalled
+            || transition
+            || level_entry
+            || after_split
+            || results_screen,
+    )
+}
+
+/// Whether game time is left paused as a run gets going (on its start, or when attaching mid-run).
+/// It has to be running from the start unless a load is going on: without any load removal
+/// enabled, nothing would resume it later on
+fn starts_paused(watchers: &Watchers, settings: &Settings) -> bool {
+    is_loading(watchers, settings).eq(&Some(true))
 }
 
-fn update_loop(process: &Process, memory: &Memory, watchers: &mut Watchers) {
-    watchers
-        .game_status
-        .update_infallible(match process.read::<u32>(memory.game_status) {
-            Ok(2) => GameStatus::DemoMode,
-            Ok(3) => GameStatus::MainMenu,
-            Ok(5) => GameStatus::InGame,
-            Ok(8) => GameStatus::WorldMap,
-            Ok(12) => GameStatus::Intro,
-            _ => GameStatus::Unknown,
+fn split(
+    watchers: &Watchers,
+    settings: &Settings,
+    split_levels: &SplitLevels,
+    run_state: &RunState,
+) -> bool {
+    // A completion flag left over from a previous session could otherwise split on the very first frame
+    let armed = run_state.game_time >= settings.split_arm_delay.into();
+
+    let level_completed = armed && level_completed(watchers);
+
+    // Two splits per level: one when leaving the world map, one when getting back to it. The
+    // transitions don't involve the completion flag, so the arm delay doesn't apply to them: the
+    // first level entry can come right after the start and still has to split. The final boss
+    // never goes back to the world map, so its completion splits instead. Backing out of a level
+    // doesn't finish it, so neither leaving it that way nor entering a level again splits. On
+    // leaving a level, level_exit already tells how it was left this very tick
+    if settings.split_map_transitions {
+        let backed_out = watchers.level_exit.eq(&Some(LevelExit::BackedOut));
+        return watchers.game_status.pair.is_some_and(|val| {
+            (val.changed_from_to(&GameStatus::WorldMap, &GameStatus::InGame)
+                || val.changed_from_to(&GameStatus::InGame, &GameStatus::WorldMap))
+                && !backed_out
+        }) || (level_completed
+            && level_for_split(watchers.level.pair.as_ref())
+                .is_some_and(|level| !level.returns_to_world_map()));
+    }
+
+    if !armed {
+        return false;
+    }
+
+    // In race practice the run is a single level, so its completion always ends it
+    if settings.race_practice {
+        return level_completed;
+    }
+
+    let completed_level = match level_completed {
+        true => level_for_split(watchers.level.pair.as_ref()),
+        false => None,
+    };
+
+    // Some levels raise the flag at a scripted cutscene, ahead of the actual end of the
+    // level. Optionally, their split is held back by a delay to get closer to the true end
+    let delayed = |level: Level| {
+        settings.delay_completion_split && LevelSettings::of(level, settings).delay_completion
+    };
+
+    let completed_level = completed_level
+        .filter(|&level| !delayed(level))
+        .or_else(|| {
+            let delay = settings.completion_split_delay.into();
+            watchers
+                .last_completion
+                .filter(|&(level, time)| {
+                    delayed(level) && time >= delay && time - tick_duration() < delay
+                })
+                .map(|(level, _)| level)
         });
 
-    watchers.level_complete_flag.update_infallible(
-        process
-            .read::<u8>(memory.level_completion_flag)
-            .is_ok_and(|val| val != 0),
-    );
+    // Splits can be moved from the completion to the return to the world map: for boss
+    // levels, to include the walk back to the hub after the fight, or for every level, so
+    // that only completions confirmed by reaching the map count
+    let deferred = |level: Level| {
+        level.returns_to_world_map()
+            && (settings.confirm_split_on_world_map
+                || (settings.split_boss_on_hub && level.is_boss()))
+    };
 
-    watchers
-        .level
-        .update_infallible(match process.read::<u32>(memory.level_id) {
-            Ok(10) => Level::L1_1,
-            Ok(11) => Level::L1_2,
-            Ok(12) => Level::L1_3,
-            Ok(13) => Level::L1_B1,
-            Ok(14) => Level::L1_4,
-            Ok(15) => Level::L1_5,
-            Ok(16) => Level::L1_6,
-            Ok(17) => Level::L1_B2,
-            Ok(18) => Level::L1_S1,
-            Ok(19) => Level::L1_S2,
-            Ok(20) => Level::L2_1,
-            Ok(21) => Level::L2_2,
-            Ok(22) => Level::L2_3,
-            Ok(23) => Level::L2_B1,
-            Ok(24) => Level::L2_4,
-            Ok(25) => Level::L2_5,
-            Ok(26) => Level::L2_6,
-            Ok(27) => Level::L2_B2,
-            Ok(28) => Level::L2_S1,
-            Ok(29) => Level::L2_S2,
-            Ok(30) => Level::L3_1,
-            Ok(31) => Level::L3_2,
-            Ok(32) => Level::L3_3,
-            Ok(33) => Level::L3_B1,
-            Ok(34) => Level::L3_4,
-            Ok(35) => Level::L3_5,
-            Ok(36) => Level::L3_6,
-            Ok(37) => Level::L3_B2,
-            Ok(38) => Level::L3_S1,
-            Ok(39) => Level::L3_S2,
-            Ok(40) => Level::L4_1,
-            Ok(41) => Level::L4_2,
-            Ok(42) => Level::L4_3,
-            Ok(43) => Level::L4_B1,
-            Ok(44) => Level::L4_4,
-            Ok(45) => Level::L4_5,
-            Ok(46) => Level::L4_6,
-            Ok(47) => Level::L4_B2,
-            Ok(48) => Level::L4_S1,
-            Ok(49) => Level::L4_S2,
-            Ok(50) => Level::L5_1,
-            Ok(51) => Level::L5_2,
-            Ok(52) => Level::L5_3,
-            Ok(53) => Level::L5_4,
-            Ok(54) => Level::L5_B1,
-            _ => Level::L1_1,
+    // On fast transitions, the completion and the return to the world map can fall on the same
+    // tick. The deferred split is still picked up then, rather than being dropped with the completion
+    let completed_level = completed_level
+        .filter(|&level| !deferred(level))
+        .or_else(|| {
+            watchers
+                .game_status
+                .pair
+                .is_some_and(|val| val.changed_from_to(&GameStatus::InGame, &GameStatus::WorldMap))
+                .then_some(watchers.last_completed_level)
+                .flatten()
+                // A back-out never has a completed level to split on
+                .filter(|&level| deferred(level))
         });
+
+    completed_level.is_some_and(|level| split_levels.contains(level))
 }
 
-fn start(watchers: &Watchers, settings: &Settings) -> bool {
-    if !settings.start {
+/// Splits off the startup segment of the run once the player first gets control in a level.
+/// Takes precedence over split(), so a level entry splitting in its own right doesn't split twice
+fn start_split(watchers: &Watchers, run_state: &RunState) -> bool {
+    run_state.start_split_pending
+        && watchers
+            .game_status
+            .pair
+            .is_some_and(|val| val.changed_from_to(&GameStatus::WorldMap, &GameStatus::InGame))
+}
+
+/// Set of the levels whose completion triggers a split, indexed by [`Level::ordinal`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct SplitLevels(u64);
+
+impl SplitLevels {
+    /// A custom split sequence overrides the settings, leaving only its next level in the set
+    fn new(settings: &Settings, sequence: &SplitSequence, position: usize) -> Self {
+        if sequence.is_active() {
+            return Self(
+                sequence
+                    .next(position)
+                    .map_or(0, |level| 1 << level.ordinal()),
+            );
+        }
+
+        Self(
+            LEVELS
+                .iter()
+                .filter(|&&level| level_enabled(level, settings))
+                .fold(0, |set, level| set | (1 << level.ordinal())),
+        )
+    }
+
+    const fn contains(&self, level: Level) -> bool {
+        self.0 & (1 << level.ordinal()) != 0
+    }
+
+    /// Last level of the set, see [`Level::ordinal`]
+    fn last(&self) -> Option<Level> {
+        LEVELS
+            .iter()
+            .rev()
+            .copied()
+            .find(|&level| self.contains(level))
+    }
+}
+
+/// Levels to split on in their expected order of completion, as defined by the user
+#[derive(Default)]
+struct SplitSequence {
+    /// Stored value the levels were parsed from
+    raw: ArrayString<256>,
+    levels: ArrayVec<Level, 64>,
+    /// The stored value is too long to be picked up
+    too_long: bool,
+}
+
+impl SplitSequence {
+    /// Picks up the stored sequence, parsing it again whenever it's been edited
+    fn update(&mut self, map: &settings::Map) {
+        let stored = map
+            .get(SPLIT_SEQUENCE_KEY)
+            .and_then(|val| val.get_array_string::<256>());
+
+        // Reported once, rather than on every tick the value stays the same
+        let too_long = matches!(stored, Some(Err(_)));
+        if too_long && !self.too_long {
+            asr::print_limited::<128>(&format_args!(
+                "Custom split sequence ignored: it goes past {} characters",
+                self.raw.capacity()
+            ));
+        }
+        self.too_long = too_long;
+
+        let raw = stored.and_then(Result::ok).unwrap_or_default();
+
+        if raw == self.raw {
+            return;
+        }
+        self.raw = raw;
+
+        // A sequence with mistakes in it would put every following split out of step,
+        // so it's ignored as a whole rather than skipping the faulty entries
+        self.levels = match Self::parse(&self.raw) {
+            Ok(levels) => levels,
+            Err(entry) => {
+                asr::print_limited::<128>(&format_args!(
+                    "Custom split sequence ignored: \"{}\" is not a level code or goes past {} entries",
+                    entry,
+                    self.levels.capacity()
+                ));
+                ArrayVec::new()
+            }
+        };
+    }
+
+    /// Parses level codes separated by commas (eg. "1-1,1-3,2-1"). Returns the faulty entry on failure
+    fn parse(raw: &str) -> Result<ArrayVec<Level, 64>, &str> {
+        let mut levels = ArrayVec::new();
+        for entry in raw
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let level = LEVELS
+                .iter()
+                .copied()
+                .find(|level| level.code().eq_ignore_ascii_case(entry))
+                .ok_or(entry)?;
+            levels.try_push(level).map_err(|_| entry)?;
+        }
+        Ok(levels)
+    }
+
+    fn is_active(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    /// Position of the first entry for the level, starting from the given position
+    fn find(&self, level: Level, from: usize) -> Option<usize> {
+        self.levels
+            .get(from..)?
+            .iter()
+            .position(|&entry| entry == level)
+            .map(|offset| from + offset)
+    }
+
+    /// Level to split on next. `None` once the whole sequence has been split
+    fn next(&self, position: usize) -> Option<Level> {
+        self.levels.get(position).copied()
+    }
+}
+
+/// Whether completing the level should split, according to the selected category.
+///
+/// The auto splitter can't see the segments of the loaded splits, so instead of matching
+/// them automatically, a category preset keeps the enabled levels in line with the layout.
+/// The transit levels only apply to the presets, a custom selection simply leaves them unchecked
+fn level_enabled(level: Level, settings: &Settings) -> bool {
+    let level_settings = LevelSettings::of(level, settings);
+    match settings.category {
+        Category::AllLevels => !level_settings.transit,
+        Category::NoSecretLevels => !level.is_secret() && !level_settings.transit,
+        Category::Custom => level_settings.split,
+    }
+}
+
+/// Settings of an individual level
+struct LevelSettings {
+    /// Split on its completion, with the custom category
+    split: bool,
+    pause_on_entry: bool,
+    /// Never split on its completion, with the preset categories
+    transit: bool,
+    delay_completion: bool,
+}
+
+impl LevelSettings {
+    /// Every per-level setting comes from this single table, keeping them all in sync
+    fn of(level: Level, s: &Settings) -> Self {
+        let (split, pause_on_entry, transit, delay_completion) = match level {
+            Level::L1_1 => (s.level_1_1, s.pause_1_1, s.skip_1_1, s.delay_1_1),
+            Level::L1_2 => (s.level_1_2, s.pause_1_2, s.skip_1_2, s.delay_1_2),
+            Level::L1_3 => (s.level_1_3, s.pause_1_3, s.skip_1_3, s.delay_1_3),
+            Level::L1_B1 => (s.level_1_b1, s.pause_1_b1, s.skip_1_b1, s.delay_1_b1),
+            Level::L1_S1 => (s.level_1_s1, s.pause_1_s1, s.skip_1_s1, s.delay_1_s1),
+            Level::L1_4 => (s.level_1_4, s.pause_1_4, s.skip_1_4, s.delay_1_4),
+            Level::L1_5 => (s.level_1_5, s.pause_1_5, s.skip_1_5, s.delay_1_5),
+            Level::L1_6 => (s.level_1_6, s.pause_1_6, s.skip_1_6, s.delay_1_6),
+            Level::L1_B2 => (s.level_1_b2, s.pause_1_b2, s.skip_1_b2, s.delay_1_b2),
+            Level::L1_S2 => (s.level_1_s2, s.pause_1_s2, s.skip_1_s2, s.delay_1_s2),
+            Level::L2_1 => (s.level_2_1, s.pause_2_1, s.skip_2_1, s.delay_2_1),
+            Level::L2_2 => (s.level_2_2, s.pause_2_2, s.skip_2_2, s.delay_2_2),
+            Level::L2_3 => (s.level_2_3, s.pause_2_3, s.skip_2_3, s.delay_2_3),
+            Level::L2_B1 => (s.level_2_b1, s.pause_2_b1, s.skip_2_b1, s.delay_2_b1),
+            Level::L2_S1 => (s.level_2_s1, s.pause_2_s1, s.skip_2_s1, s.delay_2_s1),
+            Level::L2_4 => (s.level_2_4, s.pause_2_4, s.skip_2_4, s.delay_2_4),
+            Level::L2_5 => (s.level_2_5, s.pause_2_5, s.skip_2_5, s.delay_2_5),
+            Level::L2_6 => (s.level_2_6, s.pause_2_6, s.skip_2_6, s.delay_2_6),
+            Level::L2_B2 => (s.level_2_b2, s.pause_2_b2, s.skip_2_b2, s.delay_2_b2),
+            Level::L2_S2 => (s.level_2_s2, s.pause_2_s2, s.skip_2_s2, s.delay_2_s2),
+            Level::L3_1 => (s.level_3_1, s.pause_3_1, s.skip_3_1, s.delay_3_1),
+            Level::L3_2 => (s.level_3_2, s.pause_3_2, s.skip_3_2, s.delay_3_2),
+            Level::L3_3 => (s.level_3_3, s.pause_3_3, s.skip_3_3, s.delay_3_3),
+            Level::L3_B1 => (s.level_3_b1, s.pause_3_b1, s.skip_3_b1, s.delay_3_b1),
+            Level::L3_S1 => (s.level_3_s1, s.pause_3_s1, s.skip_3_s1, s.delay_3_s1),
+            Level::L3_4 => (s.level_3_4, s.pause_3_4, s.skip_3_4, s.delay_3_4),
+            Level::L3_5 => (s.level_3_5, s.pause_3_5, s.skip_3_5, s.delay_3_5),
+            Level::L3_6 => (s.level_3_6, s.pause_3_6, s.skip_3_6, s.delay_3_6),
+            Level::L3_B2 => (s.level_3_b2, s.pause_3_b2, s.skip_3_b2, s.delay_3_b2),
+            Level::L3_S2 => (s.level_3_s2, s.pause_3_s2, s.skip_3_s2, s.delay_3_s2),
+            Level::L4_1 => (s.level_4_1, s.pause_4_1, s.skip_4_1, s.delay_4_1),
+            Level::L4_2 => (s.level_4_2, s.pause_4_2, s.skip_4_2, s.delay_4_2),
+            Level::L4_3 => (s.level_4_3, s.pause_4_3, s.skip_4_3, s.delay_4_3),
+            Level::L4_B1 => (s.level_4_b1, s.pause_4_b1, s.skip_4_b1, s.delay_4_b1),
+            Level::L4_S1 => (s.level_4_s1, s.pause_4_s1, s.skip_4_s1, s.delay_4_s1),
+            Level::L4_4 => (s.level_4_4, s.pause_4_4, s.skip_4_4, s.delay_4_4),
+            Level::L4_5 => (s.level_4_5, s.pause_4_5, s.skip_4_5, s.delay_4_5),
+            Level::L4_6 => (s.level_4_6, s.pause_4_6, s.skip_4_6, s.delay_4_6),
+            Level::L4_B2 => (s.level_4_b2, s.pause_4_b2, s.skip_4_b2, s.delay_4_b2),
+            Level::L4_S2 => (s.level_4_s2, s.pause_4_s2, s.skip_4_s2, s.delay_4_s2),
+            Level::L5_1 => (s.level_5_1, s.pause_5_1, s.skip_5_1, s.delay_5_1),
+            Level::L5_2 => (s.level_5_2, s.pause_5_2, s.skip_5_2, s.delay_5_2),
+            Level::L5_3 => (s.level_5_3, s.pause_5_3, s.skip_5_3, s.delay_5_3),
+            Level::L5_4 => (s.level_5_4, s.pause_5_4, s.skip_5_4, s.delay_5_4),
+            Level::L5_B1 => (s.level_5_b1, s.pause_5_b1, s.skip_5_b1, s.delay_5_b1),
+        };
+
+        Self {
+            split,
+            pause_on_entry,
+            transit,
+            delay_completion,
+        }
+    }
+}
+
+/// Returns the level a completion flag rise belongs to.
+///
+/// The flag is raised on the same tick the game may already be switching `level_id`
+/// over to whatever comes next (the world map's selection or the following level),
+/// so the current value can't be trusted. The previous tick's value is always the
+/// level that was actually being played when the flag rose.
+fn level_for_split(pair: Option<&Pair<Option<Level>>>) -> Option<Level> {
+    pair.and_then(|val| val.old)
+}
+
+fn game_time(_watchers: &Watchers, _settings: &Settings) -> Option<Duration> {
+    None
+}
+
+fn reset(watchers: &Watchers, settings: &Settings, run_state: &RunState) -> bool {
+    // Once the run has been completed, the game heading back to the menus is expected and
+    // the finished run has to be left alone until the user saves it
+    if run_state.run_finished {
         return false;
     }
 
-    watchers
-        .game_status
-        .pair
-        .is_some_and(|val| val.changed_from_to(&GameStatus::MainMenu, &GameStatus::WorldMap))
+    // Getting back to the world map without the run having ended means the level was abandoned
+    if settings.race_practice
         && watchers
-            .level
+            .game_status
             .pair
-            .is_some_and(|val| val.current.eq(&Level::L1_1))
-}
+            .is_some_and(|val| val.changed_from_to(&GameStatus::InGame, &GameStatus::WorldMap))
+    {
+        return true;
+    }
 
-fn is_loading(_watchers: &Watchers, _settings: &Settings) -> Option<bool> {
-    None
+    settings.level_regression.eq(&LevelRegression::Reset) && level_regressed(watchers, run_state)
 }
 
-fn split(watchers: &Watchers, settings: &Settings) -> bool {
+/// Whether the level just entered is behind the furthest one completed in the route, eg. after a
+/// crash recovery or a debug warp. Only actual level entries count, as the level ID also follows
+/// the cursor on the world map. Secret levels unlock late, so going back for them is expected
+fn level_regressed(watchers: &Watchers, run_state: &RunState) -> bool {
+    let Some(furthest) = run_state.furthest_level else {
+        return false;
+    };
+
     watchers
         .game_status
         .pair
-        .is_some_and(|val| val.current.eq(&GameStatus::InGame))
+        .is_some_and(|val| val.changed_from_to(&GameStatus::WorldMap, &GameStatus::InGame))
         && watchers
-            .level_complete_flag
+            .level
             .pair
-            .is_some_and(|val| val.changed_from_to(&false, &true))
-        && match watchers.level.pair.map(|val| val.old) {
-            Some(Level::L1_1) => settings.level_1_1,
-            Some(Level::L1_2) => settings.level_1_2,
-            Some(Level::L1_3) => settings.level_1_3,
-            Some(Level::L1_4) => settings.level_1_4,
-            Some(Level::L1_5) => settings.level_1_5,
-            Some(Level::L1_6) => settings.level_1_6,
-            Some(Level::L1_B1) => settings.level_1_b1,
-            Some(Level::L1_B2) => settings.level_1_b2,
-            Some(Level::L1_S1) => settings.level_1_s1,
-            Some(Level::L1_S2) => settings.level_1_s2,
-            Some(Level::L2_1) => settings.level_2_1,
-            Some(Level::L2_2) => settings.level_2_2,
-            Some(Level::L2_3) => settings.level_2_3,
-            Some(Level::L2_4) => settings.level_2_4,
-            Some(Level::L2_5) => settings.level_2_5,
-            Some(Level::L2_6) => settings.level_2_6,
-            Some(Level::L2_B1) => settings.level_2_b1,
-            Some(Level::L2_B2) => settings.level_2_b2,
-            Some(Level::L2_S1) => settings.level_2_s1,
-            Some(Level::L2_S2) => settings.level_2_s2,
-            Some(Level::L3_1) => settings.level_3_1,
-            Some(Level::L3_2) => settings.level_3_2,
-            Some(Level::L3_3) => settings.level_3_3,
-            Some(Level::L3_4) => settings.level_3_4,
-            Some(Level::L3_5) => settings.level_3_5,
-            Some(Level::L3_6) => settings.level_3_6,
-            Some(Level::L3_B1) => settings.level_3_b1,
-            Some(Level::L3_B2) => settings.level_3_b2,
-            Some(Level::L3_S1) => settings.level_3_s1,
-            Some(Level::L3_S2) => settings.level_3_s2,
-            Some(Level::L4_1) => settings.level_4_1,
-            Some(Level::L4_2) => settings.level_4_2,
-            Some(Level::L4_3) => settings.level_4_3,
-            Some(Level::L4_4) => settings.level_4_4,
-            Some(Level::L4_5) => settings.level_4_5,
-            Some(Level::L4_6) => settings.level_4_6,
-            Some(Level::L4_B1) => settings.level_4_b1,
-            Some(Level::L4_B2) => settings.level_4_b2,
-            Some(Level::L4_S1) => settings.level_4_s1,
-            Some(Level::L4_S2) => settings.level_4_s2,
-            Some(Level::L5_1) => settings.level_5_1,
-            Some(Level::L5_2) => settings.level_5_2,
-            Some(Level::L5_3) => settings.level_5_3,
-            Some(Level::L5_4) => settings.level_5_4,
-            Some(Level::L5_B1) => settings.level_5_b1,
-            _ => false,
-        }
-}
-
-fn game_time(_watchers: &Watchers, _settings: &Settings, _addresses: &Memory) -> Option<Duration> {
-    None
+            .and_then(|val| val.current)
+            .is_some_and(|level| !level.is_secret() && level.ordinal() < furthest.ordinal())
 }
 
-fn reset(_watchers: &Watchers, _settings: &Settings) -> bool {
-    false
+#[cfg(test)]
+mod tests {
+    //! The splitter builds for wasm by default, so these run with the host as the target
+    //! instead, eg. `cargo test --target x86_64-unknown-linux-gnu`
+
+    use super::*;
+
+    /// Declares the default of every setting, as the settings GUI has it
+    macro_rules! settings_defaults {
+        ($($field:ident: $value:expr,)*) => {
+            impl Default for Settings {
+                fn default() -> Self {
+                    Self { $($field: $value,)* }
+                }
+            }
+
+            /// Field names and default values, see [`settings_defaults_match_the_gui`]
+            const SETTINGS_DEFAULTS: &[(&str, &str)] =
+                &[$((stringify!($field), stringify!($value)),)*];
+        };
+    }
+
+    settings_defaults! {
+        _general: Title,
+        start: true,
+        start_on_intro_end: false,
+        race_practice: false,
+        keep_run_state_on_reattach: true,
+        ignore_demo_mode: true,
+        _load_removal: Title,
+        pause_on_level_load: false,
+        level_load_pause: ShortDuration::OneSecond,
+        pause_on_level_exit: false,
+        level_exit_pause: ShortDuration::OneSecond,
+        pause_on_menu_transition: false,
+        menu_transition_pause: ShortDuration::OneSecond,
+        pause_during_intro: false,
+        pause_outside_gameplay: false,
+        pause_after_split: false,
+        post_split_pause: ShortDuration::OneSecond,
+        post_split_pause_until_level_load: false,
+        pause_on_results_screen: false,
+        pause_on_stall: false,
+        _level_entry_pause: Title,
+        pause_on_level_entry: false,
+        level_entry_pause: ShortDuration::OneSecond,
+        pause_1_1: false,
+        pause_1_2: false,
+        pause_1_3: false,
+        pause_1_b1: false,
+        pause_1_s1: false,
+        pause_1_4: false,
+        pause_1_5: false,
+        pause_1_6: false,
+        pause_1_b2: false,
+        pause_1_s2: false,
+        pause_2_1: false,
+        pause_2_2: false,
+        pause_2_3: false,
+        pause_2_b1: false,
+        pause_2_s1: false,
+        pause_2_4: false,
+        pause_2_5: false,
+        pause_2_6: false,
+        pause_2_b2: false,
+        pause_2_s2: false,
+        pause_3_1: false,
+        pause_3_2: false,
+        pause_3_3: false,
+        pause_3_b1: false,
+        pause_3_s1: false,
+        pause_3_4: false,
+        pause_3_5: false,
+        pause_3_6: false,
+        pause_3_b2: false,
+        pause_3_s2: false,
+        pause_4_1: false,
+        pause_4_2: false,
+        pause_4_3: false,
+        pause_4_b1: false,
+        pause_4_s1: false,
+        pause_4_4: false,
+        pause_4_5: false,
+        pause_4_6: false,
+        pause_4_b2: false,
+        pause_4_s2: false,
+        pause_5_1: false,
+        pause_5_2: false,
+        pause_5_3: false,
+        pause_5_4: false,
+        pause_5_b1: false,
+        _reset: Title,
+        level_regression: LevelRegression::Ignore,
+        full_reset: true,
+        _level: Title,
+        category: Category::Custom,
+        split_boss_on_hub: false,
+        confirm_split_on_world_map: false,
+        split_arm_delay: ShortDuration::OneSecond,
+        split_map_transitions: false,
+        split_on_run_start: false,
+        split_grouping: SplitGrouping::EveryLevel,
+        level_1_1: true,
+        level_1_2: true,
+        level_1_3: true,
+        level_1_b1: true,
+        level_1_s1: true,
+        level_1_4: true,
+        level_1_5: true,
+        level_1_6: true,
+        level_1_b2: true,
+        level_1_s2: true,
+        level_2_1: true,
+        level_2_2: true,
+        level_2_3: true,
+        level_2_b1: true,
+        level_2_s1: true,
+        level_2_4: true,
+        level_2_5: true,
+        level_2_6: true,
+        level_2_b2: true,
+        level_2_s2: true,
+        level_3_1: true,
+        level_3_2: true,
+        level_3_3: true,
+        level_3_b1: true,
+        level_3_s1: true,
+        level_3_4: true,
+        level_3_5: true,
+        level_3_6: true,
+        level_3_b2: true,
+        level_3_s2: true,
+        level_4_1: true,
+        level_4_2: true,
+        level_4_3: true,
+        level_4_b1: true,
+        level_4_s1: true,
+        level_4_4: true,
+        level_4_5: true,
+        level_4_6: true,
+        level_4_b2: true,
+        level_4_s2: true,
+        level_5_1: true,
+        level_5_2: true,
+        level_5_3: true,
+        level_5_4: true,
+        level_5_b1: true,
+        _skip: Title,
+        skip_1_1: false,
+        skip_1_2: false,
+        skip_1_3: false,
+        skip_1_b1: false,
+        skip_1_s1: false,
+        skip_1_4: false,
+        skip_1_5: false,
+        skip_1_6: false,
+        skip_1_b2: false,
+        skip_1_s2: false,
+        skip_2_1: false,
+        skip_2_2: false,
+        skip_2_3: false,
+        skip_2_b1: false,
+        skip_2_s1: false,
+        skip_2_4: false,
+        skip_2_5: false,
+        skip_2_6: false,
+        skip_2_b2: false,
+        skip_2_s2: false,
+        skip_3_1: false,
+        skip_3_2: false,
+        skip_3_3: false,
+        skip_3_b1: false,
+        skip_3_s1: false,
+        skip_3_4: false,
+        skip_3_5: false,
+        skip_3_6: false,
+        skip_3_b2: false,
+        skip_3_s2: false,
+        skip_4_1: false,
+        skip_4_2: false,
+        skip_4_3: false,
+        skip_4_b1: false,
+        skip_4_s1: false,
+        skip_4_4: false,
+        skip_4_5: false,
+        skip_4_6: false,
+        skip_4_b2: false,
+        skip_4_s2: false,
+        skip_5_1: false,
+        skip_5_2: false,
+        skip_5_3: false,
+        skip_5_4: false,
+        skip_5_b1: false,
+        _delay: Title,
+        delay_completion_split: false,
+        completion_split_delay: ShortDuration::OneSecond,
+        delay_1_1: false,
+        delay_1_2: false,
+        delay_1_3: false,
+        delay_1_b1: false,
+        delay_1_s1: false,
+        delay_1_4: false,
+        delay_1_5: false,
+        delay_1_6: false,
+        delay_1_b2: false,
+        delay_1_s2: false,
+        delay_2_1: false,
+        delay_2_2: false,
+        delay_2_3: false,
+        delay_2_b1: false,
+        delay_2_s1: false,
+        delay_2_4: false,
+        delay_2_5: false,
+        delay_2_6: false,
+        delay_2_b2: false,
+        delay_2_s2: false,
+        delay_3_1: false,
+        delay_3_2: false,
+        delay_3_3: false,
+        delay_3_b1: false,
+        delay_3_s1: false,
+        delay_3_4: false,
+        delay_3_5: false,
+        delay_3_6: false,
+        delay_3_b2: false,
+        delay_3_s2: false,
+        delay_4_1: false,
+        delay_4_2: false,
+        delay_4_3: false,
+        delay_4_b1: false,
+        delay_4_s1: false,
+        delay_4_4: false,
+        delay_4_5: false,
+        delay_4_6: false,
+        delay_4_b2: false,
+        delay_4_s2: false,
+        delay_5_1: false,
+        delay_5_2: false,
+        delay_5_3: false,
+        delay_5_4: false,
+        delay_5_b1: false,
+        _level_bests: Title,
+        track_level_bests: false,
+        clear_level_bests: false,
+        _debugging: Title,
+        verbose_logging: false,
+        dry_run: false,
+    }
+
+    /// Timer operations issued by the splitter
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    enum Action {
+        Start,
+        Split,
+        Reset,
+    }
+
+    /// Game state as read from memory: game status, completion flag and level ID, along with
+    /// the number of ticks it was held for
+    type Recording = [(u32, u32, i32, usize)];
+
+    /// Timer driven by the splitter in the tests, keeping track of what it was made to do
+    struct TestTimer {
+        /// Tick being replayed
+        tick: usize,
+        state: TimerState,
+        split_index: u64,
+        game_time_paused: bool,
+        /// Timer operations, with the tick they fell on
+        actions: Vec<(usize, Action)>,
+        /// Changes of game time between paused (`true`) and running, with the tick they fell on
+        loads: Vec<(usize, bool)>,
+    }
+
+    impl TestTimer {
+        fn new() -> Self {
+            Self {
+                tick: 0,
+                state: TimerState::NotRunning,
+                split_index: 0,
+                game_time_paused: false,
+                actions: Vec::new(),
+                loads: Vec::new(),
+            }
+        }
+
+        fn set_game_time_paused(&mut self, paused: bool) {
+            if paused != self.game_time_paused {
+                self.game_time_paused = paused;
+                self.loads.push((self.tick, paused));
+            }
+        }
+    }
+
+    impl Timer for TestTimer {
+        fn state(&self) -> TimerState {
+            self.state
+        }
+
+        fn split_index(&self) -> Option<u64> {
+            self.state
+                .ne(&TimerState::NotRunning)
+                .then_some(self.split_index)
+        }
+
+        fn start(&mut self) {
+            self.state = TimerState::Running;
+            self.split_index = 0;
+            self.actions.push((self.tick, Action::Start));
+        }
+
+        fn split(&mut self) {
+            self.split_index += 1;
+            self.actions.push((self.tick, Action::Split));
+        }
+
+        fn reset(&mut self) {
+            self.state = TimerState::NotRunning;
+            self.actions.push((self.tick, Action::Reset));
+        }
+
+        fn pause_game_time(&mut self) {
+            self.set_game_time_paused(true);
+        }
+
+        fn resume_game_time(&mut self) {
+            self.set_game_time_paused(false);
+        }
+
+        fn set_game_time(&mut self, _: Duration) {}
+
+        fn set_variable(&mut self, _: &str, _: &str) {}
+
+        fn record_level_best(&mut self, _: Level, _: Duration) -> bool {
+            false
+        }
+
+        fn print(&mut self, _: fmt::Arguments<'_>) {}
+    }
+
+    /// Replays recorded game state through the watchers and [`drive_timer`], the way the main
+    /// loop runs them on every tick
+    fn replay_timer(recording: &Recording, settings: &Settings) -> TestTimer {
+        let mut timer = TestTimer::new();
+        let mut watchers = Watchers::default();
+        let mut run_state = RunState::default();
+        let split_sequence = SplitSequence::default();
+        let mut last_tick = TimerSnapshot::take(&timer);
+
+        let ticks = recording
+            .iter()
+            .flat_map(|&(status, flag, level_id, ticks)| {
+                core::iter::repeat_n((status, flag, level_id), ticks)
+            });
+
+        for (tick, (status, flag, level_id)) in ticks.enumerate() {
+            timer.tick = tick;
+            apply_reads(
+                &mut watchers,
+                Reads {
+                    game_status: Some(status),
+                    level_completion_flag: Some(flag),
+                    level_id: Some(level_id as u32),
+                },
+            );
+            drive_timer(
+                &mut timer,
+                &mut watchers,
+                settings,
+                &split_sequence,
+                &mut run_state,
+                &mut last_tick,
+            );
+        }
+        timer
+    }
+
+    /// The timer operations of a replay, with the tick they fell on
+    fn replay(recording: &Recording, settings: &Settings) -> Vec<(usize, Action)> {
+        replay_timer(recording, settings).actions
+    }
+
+    const MAIN_MENU: u32 = 3;
+    const IN_GAME: u32 = 5;
+    const WORLD_MAP: u32 = 8;
+    /// Level ID held outside of levels
+    const NO_LEVEL: i32 = -1;
+
+    /// A new game going through 1-1 and into 1-2
+    const NEW_GAME_1_1_TO_1_2: &Recording = &[
+        (MAIN_MENU, 0, NO_LEVEL, 60),
+        // New game, with the cursor on 1-1
+        (WORLD_MAP, 0, 10, 120),
+        (IN_GAME, 0, 10, 600),
+        // Results screen
+        (IN_GAME, 1, 10, 120),
+        // Back on the world map, with the cursor moved on to 1-2
+        (WORLD_MAP, 0, 11, 120),
+        (IN_GAME, 0, 11, 600),
+    ];
+
+    /// The defaults above repeat the ones of the settings GUI, so they're compared with the
+    /// `#[default]` attributes of the source to catch them drifting apart
+    #[test]
+    fn settings_defaults_match_the_gui() {
+        let source = include_str!("lib.rs");
+        let default_variant = |ty: &str| {
+            let body = source.split(&format!("enum {ty} {{")).nth(1).unwrap();
+            let (_, variant) = body.split_once("#[default]").unwrap();
+            variant
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with("///"))
+                .unwrap()
+                .trim_end_matches(',')
+                .to_owned()
+        };
+
+        let settings = source.split("struct Settings {").nth(1).unwrap();
+        let settings = settings.split("\n}\n").next().unwrap();
+        let mut default = None;
+        let mut expected = Vec::new();
+        for line in settings.lines().map(str::trim) {
+            if let Some(val) = line
+                .strip_prefix("#[default = ")
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                default = Some(val.to_owned());
+            } else if let Some((field, ty)) = line
+                .strip_suffix(',')
+                .and_then(|line| line.split_once(": "))
+            {
+                let val = match (default.take(), ty) {
+                    (Some(val), _) => val,
+                    (None, "Title") => ty.to_owned(),
+                    (None, _) => format!("{ty}::{}", default_variant(ty)),
+                };
+                expected.push((field.to_owned(), val));
+            }
+        }
+
+        assert_eq!(SETTINGS_DEFAULTS.len(), expected.len());
+        for (&(field, val), expected) in SETTINGS_DEFAULTS.iter().zip(expected) {
+            assert_eq!((field, val.replace(' ', "")), (&*expected.0, expected.1));
+        }
+    }
+
+    #[test]
+    fn replay_new_game_into_1_2() {
+        assert_eq!(
+            replay(NEW_GAME_1_1_TO_1_2, &Settings::default()),
+            [(60, Action::Start), (780, Action::Split)],
+        );
+    }
+
+    fn watcher<T: Copy>(old: T, current: T) -> Watcher<T> {
+        let mut watcher = Watcher::default();
+        watcher.update_infallible(old);
+        watcher.update_infallible(current);
+        watcher
+    }
+
+    /// The completion flag rising in the level, well after it was entered
+    fn completion(level: Level) -> Watchers {
+        Watchers {
+            game_status: watcher(GameStatus::InGame, GameStatus::InGame),
+            level: watcher(Some(level), Some(level)),
+            level_complete_flag: watcher(false, true),
+            time_since_level_load: Some(Duration::seconds(30)),
+            ..Default::default()
+        }
+    }
+
+    /// A run going on for long enough for the splits to be armed
+    fn run_state() -> RunState {
+        RunState {
+            game_time: Duration::seconds(60),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn level_ids_map_to_every_level_once() {
+        assert_eq!(level_from_id(10), Some(Level::L1_1));
+        assert_eq!(level_from_id(54), Some(Level::L5_B1));
+
+        let mut levels = (10..=54).filter_map(level_from_id).collect::<Vec<_>>();
+        levels.sort_by_key(|level| level.ordinal());
+        levels.dedup();
+        assert_eq!(levels.len(), LEVELS.len());
+    }
+
+    #[test]
+    fn level_id_sentinels_mean_no_level() {
+        for id in [-1, i32::MIN, 0, 1, 9] {
+            assert_eq!(level_from_id(id), None, "level ID {id}");
+        }
+    }
+
+    #[test]
+    fn only_known_game_statuses_are_decoded() {
+        for raw in [2, 3, 5, 8, 12] {
+            assert_ne!(
+                GameStatus::from_raw(raw),
+                GameStatus::Unknown,
+                "game status {raw}"
+            );
+        }
+        for raw in [0, 1, 4, 9, 0xFF] {
+            assert_eq!(
+                GameStatus::from_raw(raw),
+                GameStatus::Unknown,
+                "game status {raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn debounce_holds_a_single_default_read() {
+        let mut last_read = Some(true);
+        assert!(debounce_default(&mut last_read, false, false, Some(true)));
+        assert!(!debounce_default(&mut last_read, false, false, Some(true)));
+    }
+
+    #[test]
+    fn debounce_passes_other_reads_through() {
+        let mut last_read = Some(false);
+        assert!(debounce_default(&mut last_read, true, false, Some(false)));
+        assert_eq!(last_read, Some(true));
+
+        // Without a value accepted yet, there's nothing to hold on to
+        let mut last_read = None;
+        assert!(!debounce_default(&mut last_read, false, false, None));
+    }
+
+    #[test]
+    fn split_sequence_parses_level_codes() {
+        assert_eq!(
+            SplitSequence::parse(" 1-1, 1-3 ,,2-b1").as_deref(),
+            Ok(&[Level::L1_1, Level::L1_3, Level::L2_B1][..]),
+        );
+        assert_eq!(SplitSequence::parse("").map(|levels| levels.len()), Ok(0));
+    }
+
+    #[test]
+    fn split_sequence_reports_the_faulty_entry() {
+        assert_eq!(SplitSequence::parse("1-1,6-1,1-2"), Err("6-1"));
+        assert_eq!(SplitSequence::parse("1-1 1-2"), Err("1-1 1-2"));
+
+        let too_long = ["1-1"; 65].join(",");
+        assert_eq!(SplitSequence::parse(&too_long), Err("1-1"));
+    }
+
+    #[test]
+    fn transitions_are_classified_by_status_change() {
+        use GameStatus::*;
+
+        assert_eq!(
+            Transition::classify(WorldMap, InGame),
+            Some(Transition::LevelEntry)
+        );
+        assert_eq!(
+            Transition::classify(InGame, WorldMap),
+            Some(Transition::LevelExit)
+        );
+        for (old, current) in [
+            (MainMenu, WorldMap),
+            (MainMenu, InGame),
+            (WorldMap, MainMenu),
+            (InGame, MainMenu),
+        ] {
+            assert_eq!(
+                Transition::classify(old, current),
+                Some(Transition::Menu),
+                "{old:?} -> {current:?}"
+            );
+        }
+        for (old, current) in [
+            (InGame, InGame),
+            (Intro, MainMenu),
+            (DemoMode, MainMenu),
+            (InGame, Unknown),
+        ] {
+            assert_eq!(
+                Transition::classify(old, current),
+                None,
+                "{old:?} -> {current:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn completion_belongs_to_the_previous_level() {
+        let pair = watcher(Some(Level::L1_1), Some(Level::L1_2)).pair;
+        assert_eq!(level_for_split(pair.as_ref()), Some(Level::L1_1));
+        assert_eq!(level_for_split(None), None);
+    }
+
+    #[test]
+    fn completion_with_the_level_id_unchanged() {
+        let pair = watcher(Some(Level::L2_B1), Some(Level::L2_B1)).pair;
+        assert_eq!(level_for_split(pair.as_ref()), Some(Level::L2_B1));
+    }
+
+    #[test]
+    fn completion_with_the_level_id_cleared() {
+        // The level ID can drop to a sentinel as the level unloads, on the same tick the flag rises
+        let pair = watcher(Some(Level::L1_3), None).pair;
+        assert_eq!(level_for_split(pair.as_ref()), Some(Level::L1_3));
+    }
+
+    #[test]
+    fn replay_completion_as_the_level_id_moves_on() {
+        // The level ID switching to the next level on the very tick the flag rises
+        let recording = &[
+            (MAIN_MENU, 0, NO_LEVEL, 60),
+            (WORLD_MAP, 0, 10, 120),
+            (IN_GAME, 0, 10, 600),
+            (IN_GAME, 1, 11, 120),
+            (WORLD_MAP, 0, 11, 120),
+        ];
+
+        // Only a split on 1-1 can show up, so picking 1-2 instead would go unnoticed otherwise
+        let settings = Settings {
+            level_1_2: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            replay(recording, &settings),
+            [(60, Action::Start), (780, Action::Split)],
+        );
+    }
+
+    /// The flag rising on the very tick the game is already back on the world map, with the
+    /// cursor on the next level
+    const FAST_EXIT_FROM_1_1: &Recording = &[
+        (MAIN_MENU, 0, NO_LEVEL, 60),
+        (WORLD_MAP, 0, 10, 120),
+        (IN_GAME, 0, 10, 600),
+        (WORLD_MAP, 1, 11, 1),
+        (WORLD_MAP, 0, 11, 240),
+        (IN_GAME, 0, 11, 600),
+    ];
+
+    #[test]
+    fn replay_fast_exit_splits_once() {
+        assert_eq!(
+            replay(FAST_EXIT_FROM_1_1, &Settings::default()),
+            [(60, Action::Start), (780, Action::Split)],
+        );
+    }
+
+    #[test]
+    fn replay_fast_exit_splits_once_on_the_world_map() {
+        // The completion and the return to the world map it's deferred to fall on the same tick
+        let settings = Settings {
+            confirm_split_on_world_map: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            replay(FAST_EXIT_FROM_1_1, &settings),
+            [(60, Action::Start), (780, Action::Split)],
+        );
+    }
+
+    #[test]
+    fn replay_flag_still_set_on_level_entry() {
+        // The flag lingering from 1-1 for a moment into 1-2 mustn't be taken as its idle value
+        let recording = &[
+            (MAIN_MENU, 0, NO_LEVEL, 60),
+            (WORLD_MAP, 0, 10, 120),
+            (IN_GAME, 0, 10, 600),
+            (IN_GAME, 1, 10, 120),
+            (WORLD_MAP, 1, 11, 120),
+            (IN_GAME, 1, 11, 10),
+            (IN_GAME, 0, 11, 600),
+            (IN_GAME, 1, 11, 120),
+        ];
+        assert_eq!(
+            replay(recording, &Settings::default()),
+            [
+                (60, Action::Start),
+                (780, Action::Split),
+                (1630, Action::Split)
+            ],
+        );
+    }
+
+    #[test]
+    fn replay_non_zero_idle_value() {
+        // A build whose flag idles at 2 rather than 0
+        let recording = &[
+            (MAIN_MENU, 2, NO_LEVEL, 60),
+            (WORLD_MAP, 2, 10, 120),
+            (IN_GAME, 2, 10, 600),
+            (IN_GAME, 3, 10, 120),
+        ];
+        assert_eq!(
+            replay(recording, &Settings::default()),
+            [(60, Action::Start), (780, Action::Split)],
+        );
+    }
+
+    #[test]
+    fn replay_results_screen_after_an_early_rise() {
+        let settings = Settings {
+            pause_on_results_screen: true,
+            ..Default::default()
+        };
+        let recording = &[
+            (MAIN_MENU, 0, NO_LEVEL, 60),
+            (WORLD_MAP, 0, 10, 120),
+            (IN_GAME, 0, 10, 60),
+            // A checkpoint raising the flag right after the level load
+            (IN_GAME, 1, 10, 30),
+            (IN_GAME, 0, 10, 600),
+            (IN_GAME, 1, 10, 120),
+            (WORLD_MAP, 0, 11, 120),
+        ];
+        assert_eq!(
+            replay_timer(recording, &settings).loads,
+            [(870, true), (990, false)]
+        );
+    }
+
+    #[test]
+    fn replay_delayed_split_over_the_next_level_load() {
+        let settings = Settings {
+            delay_completion_split: true,
+            completion_split_delay: ShortDuration::TenSeconds,
+            delay_1_1: true,
+            ..Default::default()
+        };
+        // 1-1 is completed on tick 780 and 1-2 entered 2 seconds later. The ticks only add up
+        // to the delay one tick past 1980, as their duration is rounded down to the nanosecond
+        let recording = &[NEW_GAME_1_1_TO_1_2, &[(IN_GAME, 0, 11, 600)]].concat();
+        assert_eq!(
+            replay(recording, &settings),
+            [(60, Action::Start), (1981, Action::Split)],
+        );
+    }
+
+    #[test]
+    fn replay_cancelled_completion_then_back_out() {
+        let settings = Settings {
+            confirm_split_on_world_map: true,
+            ..Default::default()
+        };
+        let recording = &[
+            (MAIN_MENU, 0, NO_LEVEL, 60),
+            (WORLD_MAP, 0, 10, 120),
+            (IN_GAME, 0, 10, 600),
+            (IN_GAME, 1, 10, 60),
+            // The completion gets cancelled, then the player backs out through the pause menu
+            (IN_GAME, 0, 10, 300),
+            (WORLD_MAP, 0, 10, 120),
+        ];
+        assert_eq!(replay(recording, &settings), [(60, Action::Start)]);
+    }
+
+    #[test]
+    fn replay_map_transitions_are_not_grouped() {
+        let settings = Settings {
+            split_map_transitions: true,
+            split_grouping: SplitGrouping::EveryTwoLevels,
+            ..Default::default()
+        };
+        assert_eq!(
+            replay(NEW_GAME_1_1_TO_1_2, &settings),
+            [
+                (60, Action::Start),
+                (180, Action::Split),
+                (900, Action::Split),
+                (1020, Action::Split)
+            ],
+        );
+    }
+
+    #[test]
+    fn replay_map_transitions_skip_a_back_out() {
+        let settings = Settings {
+            split_map_transitions: true,
+            ..Default::default()
+        };
+        let recording = &[
+            (MAIN_MENU, 0, NO_LEVEL, 60),
+            (WORLD_MAP, 0, 10, 120),
+            (IN_GAME, 0, 10, 300),
+            // Backed out of 1-1, then entered again
+            (WORLD_MAP, 0, 10, 120),
+            (IN_GAME, 0, 10, 600),
+            (IN_GAME, 1, 10, 120),
+            (WORLD_MAP, 0, 11, 120),
+        ];
+        assert_eq!(
+            replay(recording, &settings),
+            [
+                (60, Action::Start),
+                (180, Action::Split),
+                (1320, Action::Split)
+            ],
+        );
+    }
+
+    #[test]
+    fn start_on_new_game() {
+        let settings = Settings::default();
+        let mut watchers = Watchers {
+            game_status: watcher(GameStatus::MainMenu, GameStatus::WorldMap),
+            level: watcher(None, Some(Level::L1_1)),
+            ..Default::default()
+        };
+        assert!(start(&watchers, &settings));
+
+        // Loading a save further in the game isn't a new run
+        watchers.level = watcher(None, Some(Level::L2_1));
+        assert!(!start(&watchers, &settings));
+    }
+
+    #[test]
+    fn start_disabled() {
+        let watchers = Watchers {
+            game_status: watcher(GameStatus::MainMenu, GameStatus::WorldMap),
+            level: watcher(None, Some(Level::L1_1)),
+            ..Default::default()
+        };
+        let settings = Settings {
+            start: false,
+            ..Default::default()
+        };
+        assert!(!start(&watchers, &settings));
+    }
+
+    #[test]
+    fn no_start_on_a_level_id_sentinel() {
+        // The level ID still holding the "no level" sentinel mustn't pass for 1-1
+        let mut watchers = Watchers::default();
+        for (status, level_id) in [
+            (MAIN_MENU, NO_LEVEL),
+            (MAIN_MENU, NO_LEVEL),
+            (WORLD_MAP, NO_LEVEL),
+        ] {
+            apply_reads(
+                &mut watchers,
+                Reads {
+                    game_status: Some(status),
+                    level_completion_flag: Some(0),
+                    level_id: Some(level_id as u32),
+                },
+            );
+        }
+        assert_eq!(watchers.level.pair.map(|val| val.current), Some(None));
+        assert!(!start(&watchers, &Settings::default()));
+    }
+
+    #[test]
+    fn no_split_outside_of_levels() {
+        let settings = Settings::default();
+        let split_levels = SplitLevels::new(&settings, &SplitSequence::default(), 0);
+        let watchers = Watchers {
+            level: watcher(None, None),
+            ..completion(Level::L1_1)
+        };
+        assert!(!split(&watchers, &settings, &split_levels, &run_state()));
+    }
+
+    #[test]
+    fn game_time_runs_from_the_start_without_load_removal() {
+        let watchers = Watchers {
+            game_status: watcher(GameStatus::MainMenu, GameStatus::WorldMap),
+            level: watcher(None, Some(Level::L1_1)),
+            ..Default::default()
+        };
+        assert_eq!(is_loading(&watchers, &Settings::default()), None);
+        assert!(!starts_paused(&watchers, &Settings::default()));
+    }
+
+    #[test]
+    fn game_time_starts_paused_during_a_load() {
+        let settings = Settings {
+            pause_outside_gameplay: true,
+            ..Default::default()
+        };
+        let mut watchers = Watchers {
+            game_status: watcher(GameStatus::MainMenu, GameStatus::WorldMap),
+            level: watcher(None, Some(Level::L1_1)),
+            ..Default::default()
+        };
+        assert!(starts_paused(&watchers, &settings));
+
+        watchers.game_status = watcher(GameStatus::WorldMap, GameStatus::InGame);
+        assert!(!starts_paused(&watchers, &settings));
+    }
+
+    #[test]
+    fn split_on_level_completion() {
+        let settings = Settings::default();
+        let split_levels = SplitLevels::new(&settings, &SplitSequence::default(), 0);
+        assert!(split(
+            &completion(Level::L1_1),
+            &settings,
+            &split_levels,
+            &run_state()
+        ));
+    }
+
+    #[test]
+    fn no_split_right_after_the_start_or_a_level_entry() {
+        let settings = Settings::default();
+        let split_levels = SplitLevels::new(&settings, &SplitSequence::default(), 0);
+        assert!(!split(
+            &completion(Level::L1_1),
+            &settings,
+            &split_levels,
+            &RunState::default()
+        ));
+
+        let watchers = Watchers {
+            time_since_level_load: Some(Duration::milliseconds(500)),
+            ..completion(Level::L1_1)
+        };
+        assert!(!split(&watchers, &settings, &split_levels, &run_state()));
+    }
+
+    #[test]
+    fn split_levels_match_the_level_settings() {
+        let settings = |category| Settings {
+            category,
+            level_1_2: false,
+            level_3_b1: false,
+            level_2_s2: false,
+            skip_2_1: true,
+            skip_4_s1: true,
+            ..Default::default()
+        };
+
+        for (category, skipped) in [
+            (Category::AllLevels, &[Level::L2_1, Level::L4_S1][..]),
+            (Category::NoSecretLevels, &[Level::L2_1]),
+            (Category::Custom, &[Level::L1_2, Level::L3_B1, Level::L2_S2]),
+        ] {
+            let split_levels = SplitLevels::new(&settings(category), &SplitSequence::default(), 0);
+            for level in LEVELS {
+                let secret_skipped = category.eq(&Category::NoSecretLevels) && level.is_secret();
+                let expected = !(skipped.contains(&level) || secret_skipped);
+                assert_eq!(split_levels.contains(level), expected, "{level:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn transit_levels_only_apply_to_presets() {
+        let enabled = |category| {
+            let settings = Settings {
+                category,
+                level_2_1: false,
+                skip_1_1: true,
+                ..Default::default()
+            };
+            [Level::L1_1, Level::L2_1].map(|level| level_enabled(level, &settings))
+        };
+
+        assert_eq!(enabled(Category::AllLevels), [false, true]);
+        assert_eq!(enabled(Category::NoSecretLevels), [false, true]);
+        assert_eq!(enabled(Category::Custom), [true, false]);
+    }
+
+    #[test]
+    fn split_levels_follow_the_sequence() {
+        let sequence = SplitSequence {
+            levels: SplitSequence::parse("1-3,2-1").unwrap(),
+            ..Default::default()
+        };
+        let settings = Settings::default();
+
+        let split_levels = SplitLevels::new(&settings, &sequence, 1);
+        assert!(split_levels.contains(Level::L2_1));
+        assert!(!split_levels.contains(Level::L1_3));
+        assert_eq!(split_levels.last(), Some(Level::L2_1));
+
+        assert_eq!(SplitLevels::new(&settings, &sequence, 2), SplitLevels(0));
+    }
+
+    #[test]
+    fn no_split_on_disabled_levels() {
+        let settings = Settings {
+            level_1_1: false,
+            ..Default::default()
+        };
+        let split_levels = SplitLevels::new(&settings, &SplitSequence::default(), 0);
+        assert!(!split(
+            &completion(Level::L1_1),
+            &settings,
+            &split_levels,
+            &run_state()
+        ));
+    }
+
+    #[test]
+    fn reset_on_abandoned_practice_level() {
+        let watchers = Watchers {
+            game_status: watcher(GameStatus::InGame, GameStatus::WorldMap),
+            level: watcher(Some(Level::L3_2), Some(Level::L3_2)),
+            ..Default::default()
+        };
+        let settings = Settings {
+            race_practice: true,
+            ..Default::default()
+        };
+        assert!(reset(&watchers, &settings, &run_state()));
+
+        // Once the level has been completed, the run is left alone
+        let finished = RunState {
+            run_finished: true,
+            ..run_state()
+        };
+        assert!(!reset(&watchers, &settings, &finished));
+    }
+
+    #[test]
+    fn auto_start_disabled_mid_run() {
+        // Only start() looks at the setting, so a run started automatically keeps being
+        // split and reset as usual after it's unchecked
+        let settings = Settings {
+            start: false,
+            ..Default::default()
+        };
+        let split_levels = SplitLevels::new(&settings, &SplitSequence::default(), 0);
+        assert!(split(
+            &completion(Level::L1_1),
+            &settings,
+            &split_levels,
+            &run_state()
+        ));
+
+        let settings = Settings {
+            race_practice: true,
+            ..settings
+        };
+        let watchers = Watchers {
+            game_status: watcher(GameStatus::InGame, GameStatus::WorldMap),
+            level: watcher(Some(Level::L3_2), Some(Level::L3_2)),
+            ..Default::default()
+        };
+        assert!(reset(&watchers, &settings, &run_state()));
+    }
+
+    #[test]
+    fn regression_on_entering_an_earlier_level() {
+        let run_state = RunState {
+            furthest_level: Some(Level::L2_1),
+            ..run_state()
+        };
+        let mut watchers = Watchers {
+            game_status: watcher(GameStatus::WorldMap, GameStatus::InGame),
+            level: watcher(Some(Level::L1_2), Some(Level::L1_2)),
+            ..Default::default()
+        };
+        assert!(level_regressed(&watchers, &run_state));
+
+        // Going back for a secret level is expected
+        watchers.level = watcher(Some(Level::L1_S1), Some(Level::L1_S1));
+        assert!(!level_regressed(&watchers, &run_state));
+
+        // The cursor moving back on the world map isn't an entry
+        watchers.game_status = watcher(GameStatus::WorldMap, GameStatus::WorldMap);
+        watchers.level = watcher(Some(Level::L2_1), Some(Level::L1_2));
+        assert!(!level_regressed(&watchers, &run_state));
+    }
+
+    #[test]
+    fn no_reset_by_default() {
+        let watchers = Watchers {
+            game_status: watcher(GameStatus::InGame, GameStatus::WorldMap),
+            level: watcher(Some(Level::L3_2), Some(Level::L1_1)),
+            ..Default::default()
+        };
+        assert!(!reset(&watchers, &Settings::default(), &run_state()));
+    }
 }